@@ -0,0 +1,63 @@
+//! Runtime API definition for answering many zenlink queries in one call.
+//!
+//! Dashboards refreshing balances, allowances and exchanges would otherwise issue a state query
+//! per value. The API is called through `state_call`, which also reads all values at the same
+//! block. A batch of more than [`MAX_QUERIES`] queries is rejected as a whole.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Codec, Decode, Encode};
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+use zenlink_dex::{ExchangeInfo, TokenInfo};
+
+/// The maximum number of queries answered by one call.
+pub const MAX_QUERIES: u32 = 256;
+
+/// A zenlink query.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum Query<AccountId, AssetId, ExchangeId> {
+	/// The info of a token.
+	TokenInfo(AssetId),
+	/// The balance of `owner` in a token. \[token_id, owner\]
+	TokenBalance(AssetId, AccountId),
+	/// The amount `spender` may transfer on behalf of `owner`. \[token_id, owner, spender\]
+	TokenAllowance(AssetId, AccountId, AccountId),
+	/// An exchange, including its reserves.
+	Exchange(ExchangeId),
+	/// The exchange trading a token, including its reserves.
+	ExchangeByTokenId(AssetId),
+}
+
+/// The answer to a [`Query`] of the same position.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum Answer<AccountId, AssetId, TokenBalance, Balance, ExchangeId> {
+	/// The answer to `Query::TokenInfo`.
+	TokenInfo(Option<TokenInfo<TokenBalance>>),
+	/// The answer to `Query::TokenBalance` and `Query::TokenAllowance`.
+	Amount(TokenBalance),
+	/// The answer to `Query::Exchange` and `Query::ExchangeByTokenId`.
+	Exchange(Option<ExchangeInfo<AccountId, AssetId, TokenBalance, Balance, ExchangeId>>),
+}
+
+/// Why a batch was not answered.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum BatchReadError {
+	/// The batch has more than [`MAX_QUERIES`] queries.
+	TooManyQueries,
+}
+
+sp_api::decl_runtime_apis! {
+	pub trait BatchReadApi<AccountId, AssetId, TokenBalance, Balance, ExchangeId> where
+		AccountId: Codec,
+		AssetId: Codec,
+		TokenBalance: Codec,
+		Balance: Codec,
+		ExchangeId: Codec,
+	{
+		/// Answer `queries`, in order, or none of them if there are more than [`MAX_QUERIES`].
+		fn batch_read(
+			queries: Vec<Query<AccountId, AssetId, ExchangeId>>,
+		) -> Result<Vec<Answer<AccountId, AssetId, TokenBalance, Balance, ExchangeId>>, BatchReadError>;
+	}
+}
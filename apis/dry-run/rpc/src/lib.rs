@@ -0,0 +1,112 @@
+//! RPC interface for dry-running extrinsics with decoded dispatch errors.
+
+use std::sync::Arc;
+
+use codec::Decode;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sc_rpc_api::DenyUnsafe;
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use canvas_dry_run_runtime_api::DryRunApi as DryRunRuntimeApi;
+use canvas_dry_run_runtime_api::DryRunResult;
+
+/// The outcome of a dry run as returned over RPC.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase", tag = "outcome")]
+pub enum DryRunOutcome {
+	/// The extrinsic was dispatched successfully.
+	Success,
+	/// The extrinsic was included but its dispatch failed.
+	#[serde(rename_all = "camelCase")]
+	Failed {
+		module_index: Option<u8>,
+		module: Option<String>,
+		error_index: Option<u8>,
+		error: Option<String>,
+	},
+	/// The extrinsic would not be included in a block.
+	Invalid {
+		reason: String,
+	},
+}
+
+impl From<DryRunResult> for DryRunOutcome {
+	fn from(result: DryRunResult) -> Self {
+		let to_string = |bytes: Vec<u8>| String::from_utf8_lossy(&bytes).into_owned();
+
+		match result {
+			DryRunResult::Success => DryRunOutcome::Success,
+			DryRunResult::Failed(info) => DryRunOutcome::Failed {
+				module_index: info.module_index,
+				module: info.module.map(to_string),
+				error_index: info.error_index,
+				error: info.error.map(to_string),
+			},
+			DryRunResult::Invalid(reason) => DryRunOutcome::Invalid { reason: to_string(reason) },
+		}
+	}
+}
+
+#[rpc]
+pub trait DryRunApi<BlockHash> {
+	/// Apply the SCALE encoded `extrinsic` on top of the state of block `at` and return the
+	/// outcome, with dispatch errors resolved to module and error names.
+	#[rpc(name = "zenlink_dryRun")]
+	fn dry_run(&self, extrinsic: Bytes, at: Option<BlockHash>) -> Result<DryRunOutcome>;
+}
+
+/// A struct that implements the [`DryRunApi`].
+pub struct DryRun<C, B> {
+	client: Arc<C>,
+	deny_unsafe: DenyUnsafe,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> DryRun<C, B> {
+	/// Create new `DryRun` with the given reference to the client.
+	pub fn new(client: Arc<C>, deny_unsafe: DenyUnsafe) -> Self {
+		Self { client, deny_unsafe, _marker: Default::default() }
+	}
+}
+
+impl<C, Block> DryRunApi<<Block as BlockT>::Hash> for DryRun<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: DryRunRuntimeApi<Block>,
+{
+	fn dry_run(&self, extrinsic: Bytes, at: Option<<Block as BlockT>::Hash>) -> Result<DryRunOutcome> {
+		// Executing extrinsics is as expensive as importing them, like `system_dryRun`.
+		self.deny_unsafe.check_if_safe()?;
+
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let extrinsic: <Block as BlockT>::Extrinsic = Decode::decode(&mut &*extrinsic)
+			.map_err(|e| RpcError {
+				code: ErrorCode::InvalidParams,
+				message: "Unable to decode extrinsic".into(),
+				data: Some(format!("{:?}", e).into()),
+			})?;
+
+		api.dry_run(&at, extrinsic)
+			.map(Into::into)
+			.map_err(runtime_error_into_rpc_err)
+	}
+}
+
+const RUNTIME_ERROR: i64 = 1;
+
+/// Converts a runtime trap into an RPC error.
+fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(RUNTIME_ERROR),
+		message: "Runtime trapped".into(),
+		data: Some(format!("{:?}", err).into()),
+	}
+}
@@ -0,0 +1,59 @@
+//! Runtime API definition for dry-running extrinsics.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode};
+use sp_runtime::{DispatchError, RuntimeDebug, traits::Block as BlockT};
+use sp_std::vec::Vec;
+
+/// The outcome of applying an extrinsic.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum DryRunResult {
+	/// The extrinsic was dispatched successfully.
+	Success,
+	/// The extrinsic was included but its dispatch failed.
+	Failed(DispatchErrorInfo),
+	/// The extrinsic would not be included in a block, e.g. because of a bad nonce.
+	Invalid(Vec<u8>),
+}
+
+/// A dispatch error with the names of the module and error resolved.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct DispatchErrorInfo {
+	/// The index of the failing module, for module errors.
+	pub module_index: Option<u8>,
+	/// The name of the failing module, if known.
+	pub module: Option<Vec<u8>>,
+	/// The index of the error within its module, for module errors.
+	pub error_index: Option<u8>,
+	/// The name of the error, e.g. `BalanceLow`, if known.
+	pub error: Option<Vec<u8>>,
+}
+
+impl DispatchErrorInfo {
+	/// Describe `error`, resolving module names with `module_name`.
+	pub fn new(error: DispatchError, module_name: impl Fn(u8) -> Option<&'static str>) -> Self {
+		let (module_index, error_index, error) = match error {
+			DispatchError::Module { index, error, message } => (Some(index), Some(error), message),
+			DispatchError::Other(message) => (None, None, Some(message)),
+			DispatchError::CannotLookup => (None, None, Some("CannotLookup")),
+			DispatchError::BadOrigin => (None, None, Some("BadOrigin")),
+		};
+
+		Self {
+			module_index,
+			module: module_index.and_then(module_name).map(|name| name.as_bytes().to_vec()),
+			error_index,
+			error: error.map(|name| name.as_bytes().to_vec()),
+		}
+	}
+}
+
+sp_api::decl_runtime_apis! {
+	pub trait DryRunApi {
+		/// Apply `extrinsic` on top of the block's state and report the outcome.
+		///
+		/// The state changes are discarded.
+		fn dry_run(extrinsic: <Block as BlockT>::Extrinsic) -> DryRunResult;
+	}
+}
@@ -0,0 +1,131 @@
+//! RPC interface describing the dispatchables of the zenlink modules.
+//!
+//! The descriptions are read from the runtime metadata at the requested block, so they always
+//! match the call indices of the running runtime.
+
+use std::sync::Arc;
+
+use codec::Decode;
+use frame_metadata::{DecodeDifferent, RuntimeMetadata, RuntimeMetadataPrefixed};
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use serde::{Deserialize, Serialize};
+use sp_api::{Metadata, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+/// Modules whose name starts with this prefix are described.
+const MODULE_PREFIX: &str = "Zenlink";
+
+/// An argument of a dispatchable.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct CallArgument {
+	/// The name of the argument.
+	pub name: String,
+	/// The type of the argument as named in the metadata, e.g. `T::AssetId`.
+	pub ty: String,
+}
+
+/// A dispatchable of a zenlink module.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CallDescription {
+	/// The name of the module, e.g. `ZenlinkAssets`.
+	pub module: String,
+	/// The index of the module, the first byte of an encoded call.
+	pub module_index: u8,
+	/// The name of the dispatchable.
+	pub call: String,
+	/// The index of the dispatchable within its module, the second byte of an encoded call.
+	pub call_index: u8,
+	/// The arguments in encoding order.
+	pub arguments: Vec<CallArgument>,
+	/// The doc comment lines of the dispatchable.
+	pub documentation: Vec<String>,
+}
+
+#[rpc]
+pub trait ZenlinkCallsApi<BlockHash> {
+	/// Describe every dispatchable of the zenlink modules in the runtime at block `at`.
+	#[rpc(name = "zenlink_calls")]
+	fn calls(&self, at: Option<BlockHash>) -> Result<Vec<CallDescription>>;
+}
+
+/// A struct that implements the [`ZenlinkCallsApi`].
+pub struct ZenlinkCalls<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> ZenlinkCalls<C, B> {
+	/// Create new `ZenlinkCalls` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block> ZenlinkCallsApi<<Block as BlockT>::Hash> for ZenlinkCalls<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: Metadata<Block>,
+{
+	fn calls(&self, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<CallDescription>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let metadata = api.metadata(&at).map_err(|e| metadata_error("Runtime trapped", e))?;
+		let metadata = RuntimeMetadataPrefixed::decode(&mut &metadata[..])
+			.map_err(|e| metadata_error("Unable to decode metadata", e))?;
+		let modules = match metadata.1 {
+			RuntimeMetadata::V12(metadata) => decoded(metadata.modules),
+			other => return Err(metadata_error("Unsupported metadata version", other)),
+		};
+
+		let mut descriptions = Vec::new();
+		for module in modules {
+			let name = decoded(module.name);
+			if !name.starts_with(MODULE_PREFIX) {
+				continue;
+			}
+
+			for (call_index, call) in module.calls.map(decoded).unwrap_or_default().into_iter().enumerate() {
+				descriptions.push(CallDescription {
+					module: name.clone(),
+					module_index: module.index,
+					call: decoded(call.name),
+					call_index: call_index as u8,
+					arguments: decoded(call.arguments)
+						.into_iter()
+						.map(|argument| CallArgument {
+							name: decoded(argument.name),
+							ty: decoded(argument.ty),
+						})
+						.collect(),
+					documentation: decoded(call.documentation),
+				});
+			}
+		}
+
+		Ok(descriptions)
+	}
+}
+
+/// The value of metadata decoded from its encoding, which is always the decoded variant.
+fn decoded<B, O: Default>(value: DecodeDifferent<B, O>) -> O {
+	match value {
+		DecodeDifferent::Decoded(value) => value,
+		DecodeDifferent::Encode(_) => Default::default(),
+	}
+}
+
+const METADATA_ERROR: i64 = 1;
+
+/// Converts a failure to read the metadata into an RPC error.
+fn metadata_error(message: &str, err: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(METADATA_ERROR),
+		message: message.into(),
+		data: Some(format!("{:?}", err).into()),
+	}
+}
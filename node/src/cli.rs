@@ -12,6 +12,9 @@ pub struct Cli {
 
 #[derive(Debug, StructOpt)]
 pub enum Subcommand {
+	/// Key management cli utilities
+	Key(sc_cli::KeySubcommand),
+
 	/// Build a chain specification.
 	BuildSpec(sc_cli::BuildSpecCmd),
 
@@ -32,4 +35,8 @@ pub enum Subcommand {
 
 	/// Revert the chain to a previous state.
 	Revert(sc_cli::RevertCmd),
+
+	/// Re-execute blocks and check the reserves of every dex exchange.
+	#[cfg(feature = "with-dex")]
+	ReplayDex(crate::replay_dex::ReplayDexCmd),
 }
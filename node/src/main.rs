@@ -7,6 +7,8 @@ mod service;
 mod cli;
 mod command;
 mod rpc;
+#[cfg(feature = "with-dex")]
+mod replay_dex;
 
 fn main() -> sc_cli::Result<()> {
 	command::run()
@@ -0,0 +1,170 @@
+//! The `replay-dex` subcommand, re-executing blocks and checking the dex invariant.
+//!
+//! Every block in the range is executed again on top of its parent's state; execution fails if
+//! the resulting state root differs from the imported one, so the state stored for the block is
+//! the state the current runtime code produces. The reserves of every exchange are then compared
+//! with the parent's.
+//!
+//! The invariant checked is the constant product of an exchange per liquidity share: swaps only
+//! grow `token_reserve * currency_reserve` through their fees, and adding or removing liquidity
+//! scales it with the square of the liquidity, rounding in favour of the exchange. So
+//! `product / liquidity²` never decreases from one block to the next.
+//!
+//! Historical states are needed, so the node's database must be an archive, e.g. synced with
+//! `--pruning archive`.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use canvas_runtime::{opaque::Block, AccountId, AssetId, Balance, BlockNumber, ExchangeId, TokenBalance};
+use sc_cli::{CliConfiguration, Error, ImportParams, SharedParams};
+use sc_client_api::BlockBackend;
+use sp_api::{Core, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_core::U512;
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, Header as HeaderT},
+};
+use structopt::StructOpt;
+use zenlink_dex_runtime_api::ZenlinkDexApi;
+
+type Exchange = zenlink_dex::ExchangeInfo<AccountId, AssetId, TokenBalance, Balance, ExchangeId>;
+
+/// The `replay-dex` command.
+#[derive(Debug, StructOpt)]
+pub struct ReplayDexCmd {
+	/// The first block to replay.
+	#[structopt(long)]
+	pub from: BlockNumber,
+
+	/// The last block to replay.
+	#[structopt(long)]
+	pub to: BlockNumber,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub import_params: ImportParams,
+}
+
+impl ReplayDexCmd {
+	/// Replay the blocks `from..=to`, print the reserve changes of every exchange and fail if
+	/// any block broke the invariant.
+	pub fn run<C>(&self, client: Arc<C>) -> sc_cli::Result<()>
+	where
+		C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + BlockBackend<Block>,
+		C::Api: Core<Block> + ZenlinkDexApi<Block, AccountId, AssetId, TokenBalance, Balance, ExchangeId>,
+	{
+		if self.from == 0 || self.from > self.to {
+			return Err(Error::Input("expected 0 < from <= to".into()));
+		}
+
+		let mut violations = 0;
+		let mut before = exchanges(&*client, self.from - 1)?;
+		for number in self.from..=self.to {
+			replay(&*client, number)?;
+			let after = exchanges(&*client, number)?;
+
+			for (token_id, exchange) in &after {
+				let previous = match before.get(token_id) {
+					Some(previous) if previous == exchange => continue,
+					Some(previous) => previous,
+					None => {
+						println!("#{} exchange of asset {}: created", number, token_id);
+						continue;
+					}
+				};
+
+				println!(
+					"#{} exchange of asset {}: token {} -> {}, currency {} -> {}, liquidity {} -> {}",
+					number,
+					token_id,
+					previous.token_reserve,
+					exchange.token_reserve,
+					previous.currency_reserve,
+					exchange.currency_reserve,
+					previous.total_liquidity,
+					exchange.total_liquidity,
+				);
+				if !invariant_holds(previous, exchange) {
+					println!("#{} exchange of asset {}: invariant violated", number, token_id);
+					violations += 1;
+				}
+			}
+
+			before = after;
+		}
+
+		if violations > 0 {
+			return Err(Error::Other(format!("{} invariant violations", violations)));
+		}
+		println!("Replayed blocks {} to {}, no invariant violations", self.from, self.to);
+		Ok(())
+	}
+}
+
+impl CliConfiguration for ReplayDexCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn import_params(&self) -> Option<&ImportParams> {
+		Some(&self.import_params)
+	}
+}
+
+fn hash<C: HeaderBackend<Block>>(client: &C, number: BlockNumber) -> sc_cli::Result<<Block as BlockT>::Hash> {
+	client.hash(number)?.ok_or_else(|| Error::Input(format!("block {} not found", number)))
+}
+
+/// Execute block `number` on top of its parent's state.
+fn replay<C>(client: &C, number: BlockNumber) -> sc_cli::Result<()>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + BlockBackend<Block>,
+	C::Api: Core<Block>,
+{
+	let parent = hash(client, number - 1)?;
+	let block = client
+		.block(&BlockId::Hash(hash(client, number)?))?
+		.ok_or_else(|| Error::Input(format!("body of block {} not found", number)))?
+		.block;
+
+	// The seal is added after execution on import, so it is not part of the executed header.
+	let (mut header, extrinsics) = block.deconstruct();
+	if header.digest().logs().last().map_or(false, |item| item.as_seal().is_some()) {
+		header.digest_mut().pop();
+	}
+
+	client
+		.runtime_api()
+		.execute_block(&BlockId::Hash(parent), Block::new(header, extrinsics))
+		.map_err(|e| Error::Other(format!("block {} failed to execute: {:?}", number, e)))
+}
+
+/// The exchanges after block `number`, by the asset they trade.
+fn exchanges<C>(client: &C, number: BlockNumber) -> sc_cli::Result<BTreeMap<AssetId, Exchange>>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: ZenlinkDexApi<Block, AccountId, AssetId, TokenBalance, Balance, ExchangeId>,
+{
+	let at = BlockId::Hash(hash(client, number)?);
+	let exchanges = client
+		.runtime_api()
+		.get_exchanges(&at)
+		.map_err(|e| Error::Other(format!("reading the exchanges of block {} failed: {:?}", number, e)))?;
+
+	Ok(exchanges.into_iter().map(|exchange| (exchange.token_id, exchange)).collect())
+}
+
+/// Whether the product of the reserves per liquidity share did not decrease from `before` to
+/// `after`, i.e. `product_after * liquidity_before² >= product_before * liquidity_after²`.
+fn invariant_holds(before: &Exchange, after: &Exchange) -> bool {
+	let product = |exchange: &Exchange| U512::from(exchange.token_reserve) * U512::from(exchange.currency_reserve);
+	let squared = |liquidity: TokenBalance| U512::from(liquidity) * U512::from(liquidity);
+
+	product(after) * squared(before.total_liquidity) >= product(before) * squared(after.total_liquidity)
+}
@@ -14,8 +14,29 @@ use sp_block_builder::BlockBuilder;
 use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
 use sp_transaction_pool::TransactionPool;
 
-use canvas_runtime::{AccountId, AssetId, Balance, BlockNumber,
-					 ExchangeId, Index, opaque::Block, TokenBalance};
+use canvas_runtime::{AccountId, Balance, BlockNumber, Index, opaque::Block};
+#[cfg(feature = "with-dex")]
+use canvas_runtime::{AssetId, Attestation, ExchangeId, TokenBalance};
+
+/// The runtime APIs of the `with-dex` runtime.
+#[cfg(feature = "with-dex")]
+pub trait DexRuntimeApi:
+	zenlink_dex_runtime_api::ZenlinkDexApi<Block, AccountId, AssetId, TokenBalance, Balance, ExchangeId> +
+	pallet_reserves_rpc::ReservesRuntimeApi<Block, AssetId, Attestation>
+{}
+
+#[cfg(feature = "with-dex")]
+impl<T> DexRuntimeApi for T where
+	T: zenlink_dex_runtime_api::ZenlinkDexApi<Block, AccountId, AssetId, TokenBalance, Balance, ExchangeId> +
+		pallet_reserves_rpc::ReservesRuntimeApi<Block, AssetId, Attestation>
+{}
+
+/// The runtime APIs of the `with-dex` runtime.
+#[cfg(not(feature = "with-dex"))]
+pub trait DexRuntimeApi {}
+
+#[cfg(not(feature = "with-dex"))]
+impl<T> DexRuntimeApi for T {}
 
 /// Full client dependencies.
 pub struct FullDeps<C, P> {
@@ -38,7 +59,10 @@ pub fn create_full<C, P>(
 	C::Api: pallet_contracts_rpc::ContractsRuntimeApi<Block, AccountId, Balance, BlockNumber>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
 	C::Api: BlockBuilder<Block>,
-	C::Api: zenlink_dex_runtime_api::ZenlinkDexApi<Block, AccountId, AssetId, TokenBalance, Balance, ExchangeId>,
+	C::Api: DexRuntimeApi,
+	C::Api: pallet_referral_rpc::ReferralRuntimeApi<Block, AccountId>,
+	C::Api: canvas_dry_run_rpc::DryRunRuntimeApi<Block>,
+	C::Api: sp_api::Metadata<Block>,
 	P: TransactionPool + 'static,
 {
 	use substrate_frame_rpc_system::{FullSystem, SystemApi};
@@ -64,9 +88,28 @@ pub fn create_full<C, P>(
 		ContractsApi::to_delegate(Contracts::new(client.clone()))
 	);
 
+	#[cfg(feature = "with-dex")]
 	io.extend_with(
 		zenlink_dex_rpc::ZenlinkDexApi::to_delegate(zenlink_dex_rpc::ZenlinkDex::new(client.clone()))
 	);
 
+	#[cfg(feature = "with-dex")]
+	io.extend_with(
+		pallet_reserves_rpc::ReservesApi::to_delegate(pallet_reserves_rpc::Reserves::new(client.clone()))
+	);
+
+	io.extend_with(
+		pallet_referral_rpc::ReferralApi::to_delegate(pallet_referral_rpc::Referral::new(client.clone()))
+	);
+
+	io.extend_with(
+		canvas_dry_run_rpc::DryRunApi::to_delegate(canvas_dry_run_rpc::DryRun::new(client.clone(), deny_unsafe))
+	);
+
+	#[cfg(feature = "with-dex")]
+	io.extend_with(
+		canvas_zenlink_calls_rpc::ZenlinkCallsApi::to_delegate(canvas_zenlink_calls_rpc::ZenlinkCalls::new(client.clone()))
+	);
+
 	io
 }
@@ -0,0 +1,337 @@
+//! # Auction Module
+//!
+//! Ascending auctions selling an amount of one zenlink asset (the lot) for another (the bid
+//! asset). Used to sell seized collateral for the owed asset without dumping it into thin dex
+//! pools.
+//!
+//! - The lot is escrowed in the module account when the auction is created.
+//! - Every bid is escrowed as well and the previous best bid is refunded.
+//! - A bid close to the end extends the auction, so late bidders can be outbid.
+//! - At the end the lot goes to the winner and the bid to the seller, or the lot back to the
+//!   seller if there was no bid. The [`AuctionHandler`] is notified either way.
+//!
+//! Other modules create auctions through the [`Auction`] trait, accounts allowed by
+//! [`Trait::CreateOrigin`] sell their own assets through `create_auction`.
+//!
+//! Auctions are settled at the start of the block they end in. To bound that work, at most
+//! [`Trait::MaxAuctionsPerBlock`] auctions can be created ending in the same block. Bids can
+//! still move an auction into a full block, but every extension costs a bid in the block before.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode};
+use frame_support::{
+	decl_error, decl_event, decl_module, decl_storage, ensure,
+	traits::{EnsureOrigin, Get},
+	transactional,
+	weights::Weight,
+};
+use frame_system::ensure_signed;
+use sp_runtime::{
+	DispatchError, DispatchResult, ModuleId, Perbill, RuntimeDebug,
+	traits::{AccountIdConversion, Saturating, Zero},
+};
+use sp_std::prelude::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+pub type AuctionId = u32;
+
+/// An auction in progress.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct AuctionInfo<AccountId, AssetId, TokenBalance, BlockNumber> {
+	/// Receives the winning bid, or the lot if there is none.
+	pub seller: AccountId,
+	/// The asset being sold.
+	pub lot_asset: AssetId,
+	/// The amount being sold.
+	pub lot_amount: TokenBalance,
+	/// The asset bids are made in.
+	pub bid_asset: AssetId,
+	/// The lowest acceptable first bid.
+	pub minimum_bid: TokenBalance,
+	/// The best bid so far and its bidder.
+	pub best_bid: Option<(AccountId, TokenBalance)>,
+	/// The block the auction is settled in.
+	pub end: BlockNumber,
+}
+
+pub type AuctionInfoOf<T> = AuctionInfo<
+	<T as frame_system::Trait>::AccountId,
+	<T as zenlink_assets::Trait>::AssetId,
+	<T as zenlink_assets::Trait>::TokenBalance,
+	<T as frame_system::Trait>::BlockNumber,
+>;
+
+/// Creates auctions on behalf of other modules.
+pub trait Auction<AccountId, AssetId, TokenBalance, BlockNumber> {
+	/// Escrow `lot_amount` of `lot_asset` from `seller` and auction it for `bid_asset` until
+	/// `end`.
+	fn new_auction(
+		seller: AccountId,
+		lot_asset: AssetId,
+		lot_amount: TokenBalance,
+		bid_asset: AssetId,
+		minimum_bid: TokenBalance,
+		end: BlockNumber,
+	) -> Result<AuctionId, DispatchError>;
+}
+
+/// Notified when an auction is settled.
+pub trait AuctionHandler<AccountId, TokenBalance> {
+	/// The auction `id` of `seller` was settled; `winner` is the winning bidder and bid.
+	fn on_auction_ended(id: AuctionId, seller: &AccountId, winner: Option<(AccountId, TokenBalance)>);
+}
+
+impl<AccountId, TokenBalance> AuctionHandler<AccountId, TokenBalance> for () {
+	fn on_auction_ended(_: AuctionId, _: &AccountId, _: Option<(AccountId, TokenBalance)>) {}
+}
+
+pub trait Trait: frame_system::Trait + zenlink_assets::Trait {
+	/// The overarching event type.
+	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+
+	/// The id of the account escrowing lots and bids.
+	type ModuleId: Get<ModuleId>;
+
+	/// How much a bid has to exceed the current best bid.
+	type MinimumIncrement: Get<Perbill>;
+
+	/// A bid this close to the end moves the end to this many blocks after the bid.
+	type ExtendPeriod: Get<Self::BlockNumber>;
+
+	/// Notified when an auction is settled.
+	type Handler: AuctionHandler<Self::AccountId, Self::TokenBalance>;
+
+	/// The origin allowed to create auctions with `create_auction`, resolving to the seller.
+	type CreateOrigin: EnsureOrigin<Self::Origin, Success = Self::AccountId>;
+
+	/// The maximum number of auctions that can be created ending in the same block.
+	type MaxAuctionsPerBlock: Get<u32>;
+}
+
+decl_storage! {
+	trait Store for Module<T: Trait> as Auction {
+		/// The auctions in progress.
+		pub Auctions get(fn auctions): map hasher(twox_64_concat) AuctionId => Option<AuctionInfoOf<T>>;
+
+		/// The id of the next auction.
+		pub NextAuctionId get(fn next_auction_id): AuctionId;
+
+		/// Index of the auctions by the block they are settled in.
+		pub AuctionEndTime: double_map hasher(twox_64_concat) T::BlockNumber, hasher(twox_64_concat) AuctionId => ();
+
+		/// The number of auctions settled in a block.
+		pub AuctionsEnding get(fn auctions_ending): map hasher(twox_64_concat) T::BlockNumber => u32;
+	}
+}
+
+decl_event! {
+	pub enum Event<T> where
+		AccountId = <T as frame_system::Trait>::AccountId,
+		AssetId = <T as zenlink_assets::Trait>::AssetId,
+		TokenBalance = <T as zenlink_assets::Trait>::TokenBalance,
+		BlockNumber = <T as frame_system::Trait>::BlockNumber,
+	{
+		/// An auction was created. \[id, seller, lot_asset, lot_amount, bid_asset, end\]
+		AuctionCreated(AuctionId, AccountId, AssetId, TokenBalance, AssetId, BlockNumber),
+		/// A bid was placed. \[id, bidder, amount, end\]
+		Bid(AuctionId, AccountId, TokenBalance, BlockNumber),
+		/// An auction was won. \[id, winner, amount\]
+		AuctionWon(AuctionId, AccountId, TokenBalance),
+		/// An auction ended without bids and the lot was returned. \[id\]
+		AuctionUnsold(AuctionId),
+		/// A transfer settling an auction failed and the amount stays in escrow.
+		/// \[id, asset_id, to, amount\]
+		SettlementTransferFailed(AuctionId, AssetId, AccountId, TokenBalance),
+	}
+}
+
+decl_error! {
+	pub enum Error for Module<T: Trait> {
+		/// There is no auction with this id.
+		AuctionNotFound,
+		/// The auction has ended.
+		AuctionEnded,
+		/// The bid is below the minimum bid or the required increment.
+		BidTooLow,
+		/// The end of an auction must be in the future.
+		InvalidEnd,
+		/// The lot amount must be positive.
+		ZeroLot,
+		/// `MaxAuctionsPerBlock` auctions already end in this block.
+		TooManyAuctionsEnding,
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
+		/// How much a bid has to exceed the current best bid.
+		const MinimumIncrement: Perbill = T::MinimumIncrement::get();
+
+		/// A bid this close to the end moves the end to this many blocks after the bid.
+		const ExtendPeriod: T::BlockNumber = T::ExtendPeriod::get();
+
+		/// The maximum number of auctions that can be created ending in the same block.
+		const MaxAuctionsPerBlock: u32 = T::MaxAuctionsPerBlock::get();
+
+		fn deposit_event() = default;
+
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			AuctionsEnding::<T>::remove(&now);
+			let mut settled: Weight = 0;
+			for (id, _) in AuctionEndTime::<T>::drain_prefix(&now) {
+				if let Some(auction) = Auctions::<T>::take(id) {
+					Self::settle(id, auction);
+					settled += 1;
+				}
+			}
+
+			T::DbWeight::get().reads_writes(1 + 3 * settled, 2 + 5 * settled)
+		}
+
+		/// Auction `lot_amount` of `lot_asset` owned by the origin for `bid_asset`, ending after
+		/// `duration` blocks.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(5, 7)]
+		pub fn create_auction(
+			origin,
+			lot_asset: T::AssetId,
+			lot_amount: T::TokenBalance,
+			bid_asset: T::AssetId,
+			minimum_bid: T::TokenBalance,
+			duration: T::BlockNumber,
+		) {
+			let seller = T::CreateOrigin::ensure_origin(origin)?;
+
+			let end = frame_system::Module::<T>::block_number().saturating_add(duration);
+			<Self as Auction<_, _, _, _>>::new_auction(
+				seller,
+				lot_asset,
+				lot_amount,
+				bid_asset,
+				minimum_bid,
+				end,
+			)?;
+		}
+
+		/// Bid `amount` on auction `id`.
+		///
+		/// The amount is escrowed and the previous best bid refunded. A bid within
+		/// `ExtendPeriod` of the end extends the auction.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(7, 9)]
+		#[transactional]
+		pub fn bid(origin, id: AuctionId, amount: T::TokenBalance) {
+			let bidder = ensure_signed(origin)?;
+
+			let mut auction = Self::auctions(id).ok_or(Error::<T>::AuctionNotFound)?;
+			let now = frame_system::Module::<T>::block_number();
+			ensure!(now < auction.end, Error::<T>::AuctionEnded);
+
+			let minimum = match &auction.best_bid {
+				Some((_, best)) => best.saturating_add(T::MinimumIncrement::get() * *best),
+				None => auction.minimum_bid,
+			};
+			ensure!(amount >= minimum && !amount.is_zero(), Error::<T>::BidTooLow);
+			if let Some((_, best)) = &auction.best_bid {
+				ensure!(amount > *best, Error::<T>::BidTooLow);
+			}
+
+			let escrow = Self::account_id();
+			zenlink_assets::Module::<T>::inner_transfer(&auction.bid_asset, &bidder, &escrow, amount)?;
+			if let Some((previous, best)) = auction.best_bid.take() {
+				zenlink_assets::Module::<T>::inner_transfer(&auction.bid_asset, &escrow, &previous, best)?;
+			}
+			auction.best_bid = Some((bidder.clone(), amount));
+
+			let extended_end = now.saturating_add(T::ExtendPeriod::get());
+			if extended_end > auction.end {
+				AuctionEndTime::<T>::remove(&auction.end, id);
+				AuctionsEnding::<T>::mutate(&auction.end, |count| *count = count.saturating_sub(1));
+				AuctionEndTime::<T>::insert(&extended_end, id, ());
+				AuctionsEnding::<T>::mutate(&extended_end, |count| *count = count.saturating_add(1));
+				auction.end = extended_end;
+			}
+
+			let end = auction.end;
+			Auctions::<T>::insert(id, auction);
+
+			Self::deposit_event(RawEvent::Bid(id, bidder, amount, end));
+		}
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// The account escrowing lots and bids.
+	pub fn account_id() -> T::AccountId {
+		T::ModuleId::get().into_account()
+	}
+
+	fn settle(id: AuctionId, auction: AuctionInfoOf<T>) {
+		// The escrowed amounts are owned by this module, so the transfers can only fail if the
+		// asset ledger was changed underneath us. Settle as much as possible in that case.
+		match &auction.best_bid {
+			Some((winner, amount)) => {
+				Self::pay_out(id, auction.lot_asset, winner, auction.lot_amount);
+				Self::pay_out(id, auction.bid_asset, &auction.seller, *amount);
+				Self::deposit_event(RawEvent::AuctionWon(id, winner.clone(), *amount));
+			}
+			None => {
+				Self::pay_out(id, auction.lot_asset, &auction.seller, auction.lot_amount);
+				Self::deposit_event(RawEvent::AuctionUnsold(id));
+			}
+		}
+
+		T::Handler::on_auction_ended(id, &auction.seller, auction.best_bid);
+	}
+
+	/// Transfer `amount` of `asset_id` from escrow to `to`, reporting a failure of auction `id`.
+	fn pay_out(id: AuctionId, asset_id: T::AssetId, to: &T::AccountId, amount: T::TokenBalance) {
+		if zenlink_assets::Module::<T>::inner_transfer(&asset_id, &Self::account_id(), to, amount).is_err() {
+			Self::deposit_event(RawEvent::SettlementTransferFailed(id, asset_id, to.clone(), amount));
+		}
+	}
+}
+
+impl<T: Trait> Auction<T::AccountId, T::AssetId, T::TokenBalance, T::BlockNumber> for Module<T> {
+	#[transactional]
+	fn new_auction(
+		seller: T::AccountId,
+		lot_asset: T::AssetId,
+		lot_amount: T::TokenBalance,
+		bid_asset: T::AssetId,
+		minimum_bid: T::TokenBalance,
+		end: T::BlockNumber,
+	) -> Result<AuctionId, DispatchError> {
+		ensure!(!lot_amount.is_zero(), Error::<T>::ZeroLot);
+		ensure!(end > frame_system::Module::<T>::block_number(), Error::<T>::InvalidEnd);
+		let ending = Self::auctions_ending(&end);
+		ensure!(ending < T::MaxAuctionsPerBlock::get(), Error::<T>::TooManyAuctionsEnding);
+
+		zenlink_assets::Module::<T>::inner_transfer(&lot_asset, &seller, &Self::account_id(), lot_amount)?;
+
+		let id = Self::next_auction_id();
+		NextAuctionId::put(id.saturating_add(1));
+
+		Auctions::<T>::insert(id, AuctionInfo {
+			seller: seller.clone(),
+			lot_asset,
+			lot_amount,
+			bid_asset,
+			minimum_bid,
+			best_bid: None,
+			end,
+		});
+		AuctionEndTime::<T>::insert(&end, id, ());
+		AuctionsEnding::<T>::insert(&end, ending.saturating_add(1));
+
+		Self::deposit_event(RawEvent::AuctionCreated(id, seller, lot_asset, lot_amount, bid_asset, end));
+
+		Ok(id)
+	}
+}
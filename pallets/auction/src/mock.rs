@@ -0,0 +1,141 @@
+//! Test utilities
+
+use crate::{AuctionHandler, AuctionId, Module, Trait};
+use frame_support::{impl_outer_event, impl_outer_origin, parameter_types, weights::Weight};
+use frame_system::EnsureSigned;
+use sp_core::H256;
+use sp_runtime::{
+	ModuleId, Perbill,
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+};
+use std::cell::RefCell;
+use zenlink_assets::AssetInfo;
+
+pub type AccountId = u64;
+pub type AssetId = u32;
+pub type TokenBalance = u64;
+pub type BlockNumber = u64;
+
+pub const SELLER: AccountId = 1;
+pub const ALICE: AccountId = 2;
+pub const BOB: AccountId = 3;
+
+impl_outer_origin! {
+	pub enum Origin for Test {}
+}
+
+mod auction {
+	pub use crate::Event;
+}
+
+impl_outer_event! {
+	pub enum TestEvent for Test {
+		frame_system<T>,
+		zenlink_assets<T>,
+		auction<T>,
+	}
+}
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct Test;
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const MaximumBlockWeight: Weight = 1024;
+	pub const MaximumBlockLength: u32 = 2 * 1024;
+	pub const AvailableBlockRatio: Perbill = Perbill::one();
+}
+
+impl frame_system::Trait for Test {
+	type BaseCallFilter = ();
+	type Origin = Origin;
+	type Call = ();
+	type Index = u64;
+	type BlockNumber = BlockNumber;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = TestEvent;
+	type BlockHashCount = BlockHashCount;
+	type MaximumBlockWeight = MaximumBlockWeight;
+	type DbWeight = ();
+	type BlockExecutionWeight = ();
+	type ExtrinsicBaseWeight = ();
+	type MaximumExtrinsicWeight = MaximumBlockWeight;
+	type MaximumBlockLength = MaximumBlockLength;
+	type AvailableBlockRatio = AvailableBlockRatio;
+	type Version = ();
+	type PalletInfo = ();
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+}
+
+impl zenlink_assets::Trait for Test {
+	type Event = TestEvent;
+	type TokenBalance = TokenBalance;
+	type AssetId = AssetId;
+}
+
+thread_local! {
+	static ENDED: RefCell<Vec<(AuctionId, AccountId, Option<(AccountId, TokenBalance)>)>> = RefCell::new(Vec::new());
+}
+
+/// Records the settled auctions.
+pub struct RecordEnded;
+
+impl AuctionHandler<AccountId, TokenBalance> for RecordEnded {
+	fn on_auction_ended(id: AuctionId, seller: &AccountId, winner: Option<(AccountId, TokenBalance)>) {
+		ENDED.with(|ended| ended.borrow_mut().push((id, *seller, winner)));
+	}
+}
+
+/// The auctions settled so far.
+pub fn ended() -> Vec<(AuctionId, AccountId, Option<(AccountId, TokenBalance)>)> {
+	ENDED.with(|ended| ended.borrow().clone())
+}
+
+parameter_types! {
+	pub const AuctionModuleId: ModuleId = ModuleId(*b"cvs/auct");
+	pub const MinimumIncrement: Perbill = Perbill::from_percent(10);
+	pub const ExtendPeriod: BlockNumber = 5;
+	pub const MaxAuctionsPerBlock: u32 = 2;
+}
+
+impl Trait for Test {
+	type Event = TestEvent;
+	type ModuleId = AuctionModuleId;
+	type MinimumIncrement = MinimumIncrement;
+	type ExtendPeriod = ExtendPeriod;
+	type Handler = RecordEnded;
+	type CreateOrigin = EnsureSigned<AccountId>;
+	type MaxAuctionsPerBlock = MaxAuctionsPerBlock;
+}
+
+pub type System = frame_system::Module<Test>;
+pub type Assets = zenlink_assets::Module<Test>;
+pub type Auction = Module<Test>;
+
+/// Issue a new asset with `supply` owned by `owner`.
+pub fn issue(owner: AccountId, supply: TokenBalance) -> AssetId {
+	let info = AssetInfo { name: *b"test asset\0\0\0\0\0\0", symbol: *b"TEST\0\0\0\0", decimals: 0 };
+	Assets::inner_issue(&owner, supply, &info)
+}
+
+/// The balance of `who` in `asset_id`.
+pub fn balance(asset_id: AssetId, who: AccountId) -> TokenBalance {
+	Assets::balance_of(&asset_id, &who)
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	ENDED.with(|ended| ended.borrow_mut().clear());
+
+	let storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	let mut ext = sp_io::TestExternalities::new(storage);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
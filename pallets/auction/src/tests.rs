@@ -0,0 +1,198 @@
+//! Tests for the auction module.
+
+use crate::{mock::*, Error, RawEvent};
+use frame_support::{assert_noop, assert_ok, traits::OnInitialize};
+use sp_runtime::DispatchError;
+
+const LOT: TokenBalance = 100;
+const MINIMUM_BID: TokenBalance = 50;
+
+/// Issue a lot asset held by the seller and a bid asset held by Alice and Bob.
+fn setup() -> (AssetId, AssetId) {
+	let lot_asset = issue(SELLER, 1_000);
+	let bid_asset = issue(ALICE, 2_000);
+	assert_ok!(Assets::inner_transfer(&bid_asset, &ALICE, &BOB, 1_000));
+	(lot_asset, bid_asset)
+}
+
+/// Create an auction of `LOT` ending at block 11.
+fn create(lot_asset: AssetId, bid_asset: AssetId) {
+	assert_ok!(Auction::create_auction(Origin::signed(SELLER), lot_asset, LOT, bid_asset, MINIMUM_BID, 10));
+}
+
+fn settle(now: BlockNumber) {
+	System::set_block_number(now);
+	Auction::on_initialize(now);
+}
+
+fn has_event(event: RawEvent<AccountId, AssetId, TokenBalance, BlockNumber>) -> bool {
+	System::events().iter().any(|record| record.event == TestEvent::auction(event.clone()))
+}
+
+#[test]
+fn create_auction_escrows_the_lot_of_the_origin() {
+	new_test_ext().execute_with(|| {
+		let (lot_asset, bid_asset) = setup();
+		create(lot_asset, bid_asset);
+
+		assert_eq!(balance(lot_asset, SELLER), 900);
+		assert_eq!(balance(lot_asset, Auction::account_id()), LOT);
+		let auction = Auction::auctions(0).unwrap();
+		assert_eq!(auction.seller, SELLER);
+		assert_eq!(auction.end, 11);
+	});
+}
+
+#[test]
+fn create_auction_requires_a_signed_origin() {
+	new_test_ext().execute_with(|| {
+		let (lot_asset, bid_asset) = setup();
+
+		assert_noop!(
+			Auction::create_auction(Origin::root(), lot_asset, LOT, bid_asset, MINIMUM_BID, 10),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn bid_escrows_the_amount() {
+	new_test_ext().execute_with(|| {
+		let (lot_asset, bid_asset) = setup();
+		create(lot_asset, bid_asset);
+
+		assert_noop!(Auction::bid(Origin::signed(ALICE), 0, MINIMUM_BID - 1), Error::<Test>::BidTooLow);
+		assert_ok!(Auction::bid(Origin::signed(ALICE), 0, MINIMUM_BID));
+
+		assert_eq!(balance(bid_asset, ALICE), 1_000 - MINIMUM_BID);
+		assert_eq!(balance(bid_asset, Auction::account_id()), MINIMUM_BID);
+		assert_eq!(Auction::auctions(0).unwrap().best_bid, Some((ALICE, MINIMUM_BID)));
+	});
+}
+
+#[test]
+fn outbidding_refunds_the_previous_bid() {
+	new_test_ext().execute_with(|| {
+		let (lot_asset, bid_asset) = setup();
+		create(lot_asset, bid_asset);
+		assert_ok!(Auction::bid(Origin::signed(ALICE), 0, 100));
+
+		// The next bid has to exceed the best bid by `MinimumIncrement`.
+		assert_noop!(Auction::bid(Origin::signed(BOB), 0, 109), Error::<Test>::BidTooLow);
+		assert_ok!(Auction::bid(Origin::signed(BOB), 0, 110));
+
+		assert_eq!(balance(bid_asset, ALICE), 1_000);
+		assert_eq!(balance(bid_asset, BOB), 890);
+		assert_eq!(balance(bid_asset, Auction::account_id()), 110);
+		assert_eq!(Auction::auctions(0).unwrap().best_bid, Some((BOB, 110)));
+	});
+}
+
+#[test]
+fn late_bid_extends_the_auction() {
+	new_test_ext().execute_with(|| {
+		let (lot_asset, bid_asset) = setup();
+		create(lot_asset, bid_asset);
+
+		System::set_block_number(8);
+		assert_ok!(Auction::bid(Origin::signed(ALICE), 0, MINIMUM_BID));
+		assert_eq!(Auction::auctions(0).unwrap().end, 13);
+
+		settle(11);
+		assert!(Auction::auctions(0).is_some());
+
+		System::set_block_number(13);
+		assert_noop!(Auction::bid(Origin::signed(BOB), 0, 100), Error::<Test>::AuctionEnded);
+	});
+}
+
+#[test]
+fn settle_with_a_bid_pays_seller_and_winner() {
+	new_test_ext().execute_with(|| {
+		let (lot_asset, bid_asset) = setup();
+		create(lot_asset, bid_asset);
+		assert_ok!(Auction::bid(Origin::signed(ALICE), 0, 100));
+
+		settle(11);
+
+		assert_eq!(Auction::auctions(0), None);
+		assert_eq!(balance(lot_asset, ALICE), LOT);
+		assert_eq!(balance(bid_asset, SELLER), 100);
+		assert_eq!(balance(lot_asset, Auction::account_id()), 0);
+		assert_eq!(balance(bid_asset, Auction::account_id()), 0);
+		assert!(has_event(RawEvent::AuctionWon(0, ALICE, 100)));
+		assert_eq!(ended(), vec![(0, SELLER, Some((ALICE, 100)))]);
+	});
+}
+
+#[test]
+fn settle_without_a_bid_returns_the_lot() {
+	new_test_ext().execute_with(|| {
+		let (lot_asset, bid_asset) = setup();
+		create(lot_asset, bid_asset);
+
+		settle(11);
+
+		assert_eq!(Auction::auctions(0), None);
+		assert_eq!(balance(lot_asset, SELLER), 1_000);
+		assert_eq!(balance(lot_asset, Auction::account_id()), 0);
+		assert!(has_event(RawEvent::AuctionUnsold(0)));
+		assert_eq!(ended(), vec![(0, SELLER, None)]);
+	});
+}
+
+#[test]
+fn settle_reports_a_failing_transfer_and_settles_the_rest() {
+	new_test_ext().execute_with(|| {
+		let (lot_asset, bid_asset) = setup();
+		create(lot_asset, bid_asset);
+		assert_ok!(Auction::bid(Origin::signed(ALICE), 0, 100));
+
+		// The escrowed lot disappears, so it can not be paid out.
+		assert_ok!(Assets::inner_transfer(&lot_asset, &Auction::account_id(), &BOB, LOT));
+
+		settle(11);
+
+		assert_eq!(Auction::auctions(0), None);
+		assert_eq!(balance(lot_asset, ALICE), 0);
+		assert_eq!(balance(bid_asset, SELLER), 100);
+		assert!(has_event(RawEvent::SettlementTransferFailed(0, lot_asset, ALICE, LOT)));
+		assert!(has_event(RawEvent::AuctionWon(0, ALICE, 100)));
+		assert_eq!(ended(), vec![(0, SELLER, Some((ALICE, 100)))]);
+	});
+}
+
+#[test]
+fn auctions_ending_per_block_are_limited() {
+	new_test_ext().execute_with(|| {
+		let (lot_asset, bid_asset) = setup();
+		create(lot_asset, bid_asset);
+		create(lot_asset, bid_asset);
+
+		assert_noop!(
+			Auction::create_auction(Origin::signed(SELLER), lot_asset, LOT, bid_asset, MINIMUM_BID, 10),
+			Error::<Test>::TooManyAuctionsEnding
+		);
+		assert_ok!(Auction::create_auction(Origin::signed(SELLER), lot_asset, LOT, bid_asset, MINIMUM_BID, 11));
+		assert_eq!(Auction::auctions(2).unwrap().end, 12);
+
+		settle(11);
+		assert_eq!(Auction::auctions_ending(11), 0);
+		assert_eq!(ended().len(), 2);
+		assert_eq!(Auction::auctions_ending(12), 1);
+	});
+}
+
+#[test]
+fn extending_an_auction_moves_it_to_the_new_end() {
+	new_test_ext().execute_with(|| {
+		let (lot_asset, bid_asset) = setup();
+		create(lot_asset, bid_asset);
+
+		System::set_block_number(8);
+		assert_ok!(Auction::bid(Origin::signed(ALICE), 0, MINIMUM_BID));
+
+		assert_eq!(Auction::auctions_ending(11), 0);
+		assert_eq!(Auction::auctions_ending(13), 1);
+	});
+}
@@ -0,0 +1,641 @@
+//! # Bridge Module
+//!
+//! A ChainBridge style bridge moving zenlink assets between this chain and Ethereum.
+//!
+//! Every bridged asset is identified by a resource id shared with the ERC20 handler contract on
+//! the other side and mapped to a local [`zenlink_assets`] asset.
+//!
+//! - Outbound: `transfer_out` burns the local asset and emits `FungibleTransfer` with a nonce
+//!   per destination chain. Relayers pick up the event and release the tokens on Ethereum.
+//! - Inbound: relayers vote on a proposal for each deposit made on Ethereum with
+//!   `acknowledge_proposal` or `reject_proposal`. Once `RelayerThreshold` relayers agree, the
+//!   proposal is approved and the asset minted to the recipient. Proposals not decided within
+//!   `ProposalLifetime` expire.
+//!
+//! Relayers, the threshold, the whitelisted chains and the resources are managed by
+//! [`Trait::AdminOrigin`]. Outbound transfers of a resource can be limited to an amount per
+//! window of blocks.
+//!
+//! Inbound mints of an asset can be limited the same way, so a compromised relayer set can not
+//! mint unlimited supply and drain local dex pools. Mints exceeding the limit are either
+//! deferred, leaving the proposal approved until anyone executes it with `execute_proposal`, or
+//! put in a queue per asset and executed in order with `execute_queued_mint` once the window has
+//! room. Approved proposals do not expire. A mint larger than the amount of a whole window could
+//! never be executed, so the proposal can not be approved until the limit is raised.
+//!
+//! Mints of an asset are deferred the same way while its [`Trait::ReserveGuard`] does not allow
+//! them, e.g. because the custodian stopped attesting its reserves. A frozen asset only holds up
+//! its own queue.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode};
+use frame_support::{
+	decl_error, decl_event, decl_module, decl_storage, ensure,
+	traits::{EnsureOrigin, Get},
+	transactional,
+};
+use frame_system::ensure_signed;
+use pallet_reserves::ReserveGuard;
+use sp_core::H160;
+use sp_runtime::{
+	DispatchError, DispatchResult, RuntimeDebug,
+	traits::{Saturating, Zero},
+};
+use sp_std::prelude::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+/// Identifies a chain connected by the bridge.
+pub type ChainId = u8;
+
+/// The nonce of a deposit, unique per source chain.
+pub type DepositNonce = u64;
+
+/// Identifies a bridged token on both sides of the bridge.
+pub type ResourceId = [u8; 32];
+
+/// The state of a proposal.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum ProposalStatus {
+	/// Collecting votes.
+	Initiated,
+	/// Approved, waiting for its mint to be executed.
+	Approved,
+	/// Rejected by the relayers.
+	Rejected,
+	/// Approved and its mint executed or queued.
+	Executed,
+}
+
+/// A deposit on another chain to be minted here.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Proposal<AccountId, TokenBalance> {
+	/// The bridged token.
+	pub resource_id: ResourceId,
+	/// The account to mint to.
+	pub recipient: AccountId,
+	/// The amount to mint.
+	pub amount: TokenBalance,
+}
+
+/// A proposal and the relayer votes on it.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct ProposalVotes<AccountId, TokenBalance, BlockNumber> {
+	/// The proposal voted on.
+	pub proposal: Proposal<AccountId, TokenBalance>,
+	/// The relayers in favour.
+	pub votes_for: Vec<AccountId>,
+	/// The relayers against.
+	pub votes_against: Vec<AccountId>,
+	/// The state of the proposal.
+	pub status: ProposalStatus,
+	/// The last block votes are accepted in.
+	pub expiry: BlockNumber,
+}
+
+/// How much can be bridged per window of blocks.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct RateLimit<TokenBalance, BlockNumber> {
+	/// The amount allowed per window.
+	pub amount: TokenBalance,
+	/// The length of a window in blocks.
+	pub period: BlockNumber,
+}
+
+/// What happens to an inbound mint exceeding the limit of its asset.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum ExcessPolicy {
+	/// Approve the proposal and leave it to be executed with `execute_proposal` once the window
+	/// has room.
+	Defer,
+	/// Approve the proposal and queue the mint until the window has room.
+	Queue,
+}
+
+/// An approved mint waiting for room in the inbound window of its asset.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct QueuedMint<AccountId, TokenBalance> {
+	/// The account to mint to.
+	pub recipient: AccountId,
+	/// The amount to mint.
+	pub amount: TokenBalance,
+}
+
+pub type ProposalVotesOf<T> = ProposalVotes<
+	<T as frame_system::Trait>::AccountId,
+	<T as zenlink_assets::Trait>::TokenBalance,
+	<T as frame_system::Trait>::BlockNumber,
+>;
+
+pub type RateLimitOf<T> = RateLimit<
+	<T as zenlink_assets::Trait>::TokenBalance,
+	<T as frame_system::Trait>::BlockNumber,
+>;
+
+pub type QueuedMintOf<T> = QueuedMint<
+	<T as frame_system::Trait>::AccountId,
+	<T as zenlink_assets::Trait>::TokenBalance,
+>;
+
+pub trait Trait: frame_system::Trait + zenlink_assets::Trait {
+	/// The overarching event type.
+	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+
+	/// The origin managing relayers, chains and resources.
+	type AdminOrigin: EnsureOrigin<Self::Origin>;
+
+	/// The id of this chain on the bridge.
+	type ChainId: Get<ChainId>;
+
+	/// The number of blocks relayers have to decide on a proposal.
+	type ProposalLifetime: Get<Self::BlockNumber>;
+
+	/// Decides whether bridged assets can be minted.
+	type ReserveGuard: ReserveGuard<Self::AssetId>;
+}
+
+decl_storage! {
+	trait Store for Module<T: Trait> as Bridge {
+		/// The relayers allowed to vote on proposals.
+		pub Relayers get(fn is_relayer): map hasher(blake2_128_concat) T::AccountId => bool;
+
+		/// The number of relayers.
+		pub RelayerCount get(fn relayer_count): u32;
+
+		/// The number of votes required to approve a proposal.
+		pub RelayerThreshold get(fn relayer_threshold): u32 = 1;
+
+		/// The whitelisted chains and the last deposit nonce used towards them.
+		pub ChainNonces get(fn chain_nonce): map hasher(twox_64_concat) ChainId => Option<DepositNonce>;
+
+		/// The local asset of a bridged resource.
+		pub Resources get(fn resource): map hasher(blake2_128_concat) ResourceId => Option<T::AssetId>;
+
+		/// The proposals per source chain and deposit nonce.
+		pub Votes get(fn votes): double_map hasher(twox_64_concat) ChainId, hasher(twox_64_concat) DepositNonce => Option<ProposalVotesOf<T>>;
+
+		/// The outbound limit of a resource.
+		pub OutboundLimits get(fn outbound_limit): map hasher(blake2_128_concat) ResourceId => Option<RateLimitOf<T>>;
+
+		/// The start of the current outbound window of a resource and the amount used in it.
+		pub OutboundUsage get(fn outbound_usage): map hasher(blake2_128_concat) ResourceId => (T::BlockNumber, T::TokenBalance);
+
+		/// The inbound limit of an asset and how excess mints are handled.
+		pub InboundLimits get(fn inbound_limit): map hasher(blake2_128_concat) T::AssetId => Option<(RateLimitOf<T>, ExcessPolicy)>;
+
+		/// The start of the current inbound window of an asset and the amount minted in it.
+		pub InboundUsage get(fn inbound_usage): map hasher(blake2_128_concat) T::AssetId => (T::BlockNumber, T::TokenBalance);
+
+		/// The mints of an asset waiting for room in its inbound window, by position in its queue.
+		pub QueuedMints get(fn queued_mint): double_map hasher(blake2_128_concat) T::AssetId, hasher(twox_64_concat) u64 => Option<QueuedMintOf<T>>;
+
+		/// The position of the oldest queued mint of an asset, executed next, and the position of
+		/// the next mint queued.
+		pub MintQueues get(fn mint_queue): map hasher(blake2_128_concat) T::AssetId => (u64, u64);
+	}
+}
+
+decl_event! {
+	pub enum Event<T> where
+		AccountId = <T as frame_system::Trait>::AccountId,
+		AssetId = <T as zenlink_assets::Trait>::AssetId,
+		TokenBalance = <T as zenlink_assets::Trait>::TokenBalance,
+		BlockNumber = <T as frame_system::Trait>::BlockNumber,
+	{
+		/// The vote threshold changed. \[threshold\]
+		RelayerThresholdChanged(u32),
+		/// A relayer was added. \[relayer\]
+		RelayerAdded(AccountId),
+		/// A relayer was removed. \[relayer\]
+		RelayerRemoved(AccountId),
+		/// A chain can now be bridged to. \[chain_id\]
+		ChainWhitelisted(ChainId),
+		/// A resource was mapped to a local asset. \[resource_id, asset_id\]
+		ResourceSet(ResourceId, AssetId),
+		/// A resource mapping was removed. \[resource_id\]
+		ResourceRemoved(ResourceId),
+		/// The outbound limit of a resource changed. \[resource_id, limit\]
+		OutboundLimitSet(ResourceId, Option<RateLimit<TokenBalance, BlockNumber>>),
+		/// The inbound limit of an asset changed. \[asset_id, limit\]
+		InboundLimitSet(AssetId, Option<(RateLimit<TokenBalance, BlockNumber>, ExcessPolicy)>),
+		/// A mint exceeding the inbound limit was queued. \[asset_id, position, recipient, amount\]
+		MintQueued(AssetId, u64, AccountId, TokenBalance),
+		/// A queued mint was executed. \[asset_id, position\]
+		QueuedMintExecuted(AssetId, u64),
+		/// Tokens were burned here to be released on another chain.
+		/// \[dest_id, nonce, resource_id, amount, recipient\]
+		FungibleTransfer(ChainId, DepositNonce, ResourceId, TokenBalance, H160),
+		/// A relayer voted for a proposal. \[src_id, nonce, relayer\]
+		VoteFor(ChainId, DepositNonce, AccountId),
+		/// A relayer voted against a proposal. \[src_id, nonce, relayer\]
+		VoteAgainst(ChainId, DepositNonce, AccountId),
+		/// A proposal was approved. \[src_id, nonce\]
+		ProposalApproved(ChainId, DepositNonce),
+		/// The mint of an approved proposal was executed or queued. \[src_id, nonce\]
+		ProposalExecuted(ChainId, DepositNonce),
+		/// A proposal was rejected. \[src_id, nonce\]
+		ProposalRejected(ChainId, DepositNonce),
+	}
+}
+
+decl_error! {
+	pub enum Error for Module<T: Trait> {
+		/// The threshold must be positive.
+		InvalidThreshold,
+		/// The account is already a relayer.
+		RelayerAlreadyExists,
+		/// The account is not a relayer.
+		RelayerInvalid,
+		/// Only relayers can vote.
+		MustBeRelayer,
+		/// The chain is this chain.
+		InvalidChainId,
+		/// The chain is already whitelisted.
+		ChainAlreadyWhitelisted,
+		/// The chain is not whitelisted.
+		ChainNotWhitelisted,
+		/// The resource is not mapped to a local asset.
+		ResourceDoesNotExist,
+		/// The asset does not exist in zenlink-assets.
+		AssetNotFound,
+		/// The relayer already voted on the proposal.
+		RelayerAlreadyVoted,
+		/// The proposal was already approved or rejected.
+		ProposalAlreadyComplete,
+		/// The proposal can not be voted on anymore.
+		ProposalExpired,
+		/// A different proposal exists for this deposit.
+		ProposalMismatch,
+		/// There is no proposal for this deposit.
+		ProposalDoesNotExist,
+		/// The proposal is not waiting for its mint to be executed.
+		ProposalNotApproved,
+		/// The amount must be positive.
+		ZeroAmount,
+		/// The transfer exceeds the limit for the current window.
+		RateLimitExceeded,
+		/// There are no queued mints of the asset.
+		QueueEmpty,
+		/// The amount exceeds the inbound limit of a whole window.
+		MintExceedsLimit,
+		/// Minting the asset is frozen by its reserve guard.
+		MintingFrozen,
+		/// The nonce of the destination chain overflowed.
+		NonceOverflow,
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
+		/// The id of this chain on the bridge.
+		const ChainId: ChainId = T::ChainId::get();
+
+		/// The number of blocks relayers have to decide on a proposal.
+		const ProposalLifetime: T::BlockNumber = T::ProposalLifetime::get();
+
+		fn deposit_event() = default;
+
+		/// Set the number of votes required to approve a proposal.
+		#[weight = 10_000 + T::DbWeight::get().writes(1)]
+		pub fn set_threshold(origin, threshold: u32) {
+			T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(threshold > 0, Error::<T>::InvalidThreshold);
+
+			RelayerThreshold::put(threshold);
+
+			Self::deposit_event(RawEvent::RelayerThresholdChanged(threshold));
+		}
+
+		/// Add a relayer.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(2, 2)]
+		pub fn add_relayer(origin, who: T::AccountId) {
+			T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(!Self::is_relayer(&who), Error::<T>::RelayerAlreadyExists);
+
+			Relayers::<T>::insert(&who, true);
+			RelayerCount::mutate(|count| *count = count.saturating_add(1));
+
+			Self::deposit_event(RawEvent::RelayerAdded(who));
+		}
+
+		/// Remove a relayer.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(2, 2)]
+		pub fn remove_relayer(origin, who: T::AccountId) {
+			T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(Self::is_relayer(&who), Error::<T>::RelayerInvalid);
+
+			Relayers::<T>::remove(&who);
+			RelayerCount::mutate(|count| *count = count.saturating_sub(1));
+
+			Self::deposit_event(RawEvent::RelayerRemoved(who));
+		}
+
+		/// Allow transfers to and from `id`.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(1, 1)]
+		pub fn whitelist_chain(origin, id: ChainId) {
+			T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(id != T::ChainId::get(), Error::<T>::InvalidChainId);
+			ensure!(!ChainNonces::contains_key(id), Error::<T>::ChainAlreadyWhitelisted);
+
+			ChainNonces::insert(id, 0);
+
+			Self::deposit_event(RawEvent::ChainWhitelisted(id));
+		}
+
+		/// Map `resource_id` to the local asset `asset_id`, which has to exist.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(1, 1)]
+		pub fn set_resource(origin, resource_id: ResourceId, asset_id: T::AssetId) {
+			T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(
+				zenlink_assets::Module::<T>::asset_info(&asset_id).is_some(),
+				Error::<T>::AssetNotFound
+			);
+
+			Resources::<T>::insert(resource_id, asset_id);
+
+			Self::deposit_event(RawEvent::ResourceSet(resource_id, asset_id));
+		}
+
+		/// Remove the mapping of `resource_id`.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(1, 1)]
+		pub fn remove_resource(origin, resource_id: ResourceId) {
+			T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(Resources::<T>::contains_key(resource_id), Error::<T>::ResourceDoesNotExist);
+
+			Resources::<T>::remove(resource_id);
+
+			Self::deposit_event(RawEvent::ResourceRemoved(resource_id));
+		}
+
+		/// Limit how much of `resource_id` can be transferred out per window, or lift the limit.
+		#[weight = 10_000 + T::DbWeight::get().writes(2)]
+		pub fn set_outbound_limit(origin, resource_id: ResourceId, limit: Option<RateLimitOf<T>>) {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			match &limit {
+				Some(limit) => OutboundLimits::<T>::insert(resource_id, limit),
+				None => OutboundLimits::<T>::remove(resource_id),
+			}
+			OutboundUsage::<T>::remove(resource_id);
+
+			Self::deposit_event(RawEvent::OutboundLimitSet(resource_id, limit));
+		}
+
+		/// Limit how much of `asset_id` can be minted by the bridge per window, or lift the limit.
+		///
+		/// Queued mints larger than the new amount hold up the queue of the asset until the limit
+		/// is raised again.
+		#[weight = 10_000 + T::DbWeight::get().writes(2)]
+		pub fn set_inbound_limit(origin, asset_id: T::AssetId, limit: Option<(RateLimitOf<T>, ExcessPolicy)>) {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			match &limit {
+				Some(limit) => InboundLimits::<T>::insert(asset_id, limit),
+				None => InboundLimits::<T>::remove(asset_id),
+			}
+			InboundUsage::<T>::remove(asset_id);
+
+			Self::deposit_event(RawEvent::InboundLimitSet(asset_id, limit));
+		}
+
+		/// Burn `amount` of the asset of `resource_id` to release it to `recipient` on chain
+		/// `dest_id`.
+		#[weight = 100_000_000 + T::DbWeight::get().reads_writes(6, 4)]
+		#[transactional]
+		pub fn transfer_out(origin, resource_id: ResourceId, amount: T::TokenBalance, recipient: H160, dest_id: ChainId) {
+			let who = ensure_signed(origin)?;
+
+			ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+			let nonce = Self::chain_nonce(dest_id).ok_or(Error::<T>::ChainNotWhitelisted)?;
+			let asset_id = Self::resource(resource_id).ok_or(Error::<T>::ResourceDoesNotExist)?;
+			Self::note_outbound(resource_id, amount)?;
+
+			zenlink_assets::Module::<T>::inner_burn(&asset_id, &who, amount)?;
+
+			let nonce = nonce.checked_add(1).ok_or(Error::<T>::NonceOverflow)?;
+			ChainNonces::insert(dest_id, nonce);
+
+			Self::deposit_event(RawEvent::FungibleTransfer(dest_id, nonce, resource_id, amount, recipient));
+		}
+
+		/// Vote for minting `amount` of the asset of `resource_id` to `recipient` for deposit
+		/// `nonce` made on chain `src_id`.
+		#[weight = 100_000_000 + T::DbWeight::get().reads_writes(6, 3)]
+		#[transactional]
+		pub fn acknowledge_proposal(
+			origin,
+			nonce: DepositNonce,
+			src_id: ChainId,
+			resource_id: ResourceId,
+			recipient: T::AccountId,
+			amount: T::TokenBalance,
+		) {
+			let who = ensure_signed(origin)?;
+			ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+			let proposal = Proposal { resource_id, recipient, amount };
+
+			Self::vote(who, nonce, src_id, proposal, true)?;
+		}
+
+		/// Vote against the proposal for deposit `nonce` made on chain `src_id`.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(5, 1)]
+		#[transactional]
+		pub fn reject_proposal(
+			origin,
+			nonce: DepositNonce,
+			src_id: ChainId,
+			resource_id: ResourceId,
+			recipient: T::AccountId,
+			amount: T::TokenBalance,
+		) {
+			let who = ensure_signed(origin)?;
+			let proposal = Proposal { resource_id, recipient, amount };
+
+			Self::vote(who, nonce, src_id, proposal, false)?;
+		}
+
+		/// Execute the mint of the approved proposal for deposit `nonce` made on chain `src_id`
+		/// if the inbound window of its asset has room for it and minting is not frozen.
+		#[weight = 100_000_000 + T::DbWeight::get().reads_writes(6, 3)]
+		#[transactional]
+		pub fn execute_proposal(origin, nonce: DepositNonce, src_id: ChainId) {
+			ensure_signed(origin)?;
+
+			let mut votes = Self::votes(src_id, nonce).ok_or(Error::<T>::ProposalDoesNotExist)?;
+			ensure!(votes.status == ProposalStatus::Approved, Error::<T>::ProposalNotApproved);
+
+			Self::execute(&votes.proposal)?;
+			votes.status = ProposalStatus::Executed;
+			Votes::<T>::insert(src_id, nonce, votes);
+
+			Self::deposit_event(RawEvent::ProposalExecuted(src_id, nonce));
+		}
+
+		/// Execute the oldest queued mint of `asset_id` if its inbound window has room for it and
+		/// minting it is not frozen.
+		#[weight = 100_000_000 + T::DbWeight::get().reads_writes(5, 4)]
+		#[transactional]
+		pub fn execute_queued_mint(origin, asset_id: T::AssetId) {
+			ensure_signed(origin)?;
+
+			let (head, next) = Self::mint_queue(asset_id);
+			let mint = QueuedMints::<T>::take(asset_id, head).ok_or(Error::<T>::QueueEmpty)?;
+			ensure!(T::ReserveGuard::can_mint(&asset_id), Error::<T>::MintingFrozen);
+			ensure!(Self::note_inbound(asset_id, mint.amount), Error::<T>::RateLimitExceeded);
+			zenlink_assets::Module::<T>::inner_mint(&asset_id, &mint.recipient, mint.amount)?;
+			MintQueues::<T>::insert(asset_id, (head.saturating_add(1), next));
+
+			Self::deposit_event(RawEvent::QueuedMintExecuted(asset_id, head));
+		}
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// The usage of a window after adding `amount`, or `None` if that exceeds `limit`.
+	fn consume(
+		limit: &RateLimitOf<T>,
+		(start, used): (T::BlockNumber, T::TokenBalance),
+		amount: T::TokenBalance,
+	) -> Option<(T::BlockNumber, T::TokenBalance)> {
+		let now = frame_system::Module::<T>::block_number();
+		let (start, used) = if now >= start.saturating_add(limit.period) {
+			(now, Zero::zero())
+		} else {
+			(start, used)
+		};
+
+		let used = used.saturating_add(amount);
+		if used > limit.amount {
+			return None;
+		}
+		Some((start, used))
+	}
+
+	/// Account `amount` to the outbound window of `resource_id`.
+	fn note_outbound(resource_id: ResourceId, amount: T::TokenBalance) -> DispatchResult {
+		if let Some(limit) = Self::outbound_limit(resource_id) {
+			let usage = Self::consume(&limit, Self::outbound_usage(resource_id), amount)
+				.ok_or(Error::<T>::RateLimitExceeded)?;
+			OutboundUsage::<T>::insert(resource_id, usage);
+		}
+
+		Ok(())
+	}
+
+	/// Account `amount` to the inbound window of `asset_id`, returning whether it fits.
+	fn note_inbound(asset_id: T::AssetId, amount: T::TokenBalance) -> bool {
+		let (limit, _) = match Self::inbound_limit(asset_id) {
+			Some(limit) => limit,
+			None => return true,
+		};
+
+		match Self::consume(&limit, Self::inbound_usage(asset_id), amount) {
+			Some(usage) => {
+				InboundUsage::<T>::insert(asset_id, usage);
+				true
+			}
+			None => false,
+		}
+	}
+
+	/// Record the vote of `who` on the proposal for deposit `nonce` of `src_id` and resolve
+	/// the proposal if the vote decides it.
+	fn vote(
+		who: T::AccountId,
+		nonce: DepositNonce,
+		src_id: ChainId,
+		proposal: Proposal<T::AccountId, T::TokenBalance>,
+		in_favour: bool,
+	) -> DispatchResult {
+		ensure!(Self::is_relayer(&who), Error::<T>::MustBeRelayer);
+		ensure!(ChainNonces::contains_key(src_id), Error::<T>::ChainNotWhitelisted);
+		ensure!(Resources::<T>::contains_key(proposal.resource_id), Error::<T>::ResourceDoesNotExist);
+
+		let now = frame_system::Module::<T>::block_number();
+		let mut votes = Self::votes(src_id, nonce).unwrap_or_else(|| ProposalVotes {
+			proposal: proposal.clone(),
+			votes_for: Vec::new(),
+			votes_against: Vec::new(),
+			status: ProposalStatus::Initiated,
+			expiry: now.saturating_add(T::ProposalLifetime::get()),
+		});
+
+		ensure!(votes.proposal == proposal, Error::<T>::ProposalMismatch);
+		ensure!(votes.status == ProposalStatus::Initiated, Error::<T>::ProposalAlreadyComplete);
+		ensure!(now <= votes.expiry, Error::<T>::ProposalExpired);
+		ensure!(
+			!votes.votes_for.contains(&who) && !votes.votes_against.contains(&who),
+			Error::<T>::RelayerAlreadyVoted
+		);
+
+		if in_favour {
+			votes.votes_for.push(who.clone());
+			Self::deposit_event(RawEvent::VoteFor(src_id, nonce, who));
+		} else {
+			votes.votes_against.push(who.clone());
+			Self::deposit_event(RawEvent::VoteAgainst(src_id, nonce, who));
+		}
+
+		let threshold = Self::relayer_threshold();
+		let relayers = Self::relayer_count();
+		if votes.votes_for.len() as u32 >= threshold {
+			votes.status = ProposalStatus::Approved;
+			Self::deposit_event(RawEvent::ProposalApproved(src_id, nonce));
+
+			match Self::execute(&votes.proposal) {
+				Ok(()) => {
+					votes.status = ProposalStatus::Executed;
+					Self::deposit_event(RawEvent::ProposalExecuted(src_id, nonce));
+				}
+				// The proposal stays approved and can be executed once the mint is allowed.
+				Err(e) if e == DispatchError::from(Error::<T>::RateLimitExceeded) ||
+					e == DispatchError::from(Error::<T>::MintingFrozen) => {}
+				Err(e) => return Err(e),
+			}
+		} else if relayers >= threshold &&
+			votes.votes_against.len() as u32 > relayers - threshold
+		{
+			votes.status = ProposalStatus::Rejected;
+			Self::deposit_event(RawEvent::ProposalRejected(src_id, nonce));
+		}
+
+		Votes::<T>::insert(src_id, nonce, votes);
+
+		Ok(())
+	}
+
+	/// Mint the tokens of an approved proposal, or queue the mint if it exceeds the inbound
+	/// limit and the asset's policy allows it.
+	fn execute(proposal: &Proposal<T::AccountId, T::TokenBalance>) -> DispatchResult {
+		let asset_id = Self::resource(proposal.resource_id).ok_or(Error::<T>::ResourceDoesNotExist)?;
+		let limit = Self::inbound_limit(asset_id);
+		if let Some((limit, _)) = &limit {
+			ensure!(proposal.amount <= limit.amount, Error::<T>::MintExceedsLimit);
+		}
+		ensure!(T::ReserveGuard::can_mint(&asset_id), Error::<T>::MintingFrozen);
+
+		let queue = matches!(limit, Some((_, ExcessPolicy::Queue)));
+		let (head, next) = Self::mint_queue(asset_id);
+		// A mint does not overtake the queued mints of its asset.
+		if !(queue && head < next) && Self::note_inbound(asset_id, proposal.amount) {
+			return zenlink_assets::Module::<T>::inner_mint(&asset_id, &proposal.recipient, proposal.amount);
+		}
+		ensure!(queue, Error::<T>::RateLimitExceeded);
+
+		MintQueues::<T>::insert(asset_id, (head, next.saturating_add(1)));
+		QueuedMints::<T>::insert(asset_id, next, QueuedMint {
+			recipient: proposal.recipient.clone(),
+			amount: proposal.amount,
+		});
+
+		Self::deposit_event(RawEvent::MintQueued(asset_id, next, proposal.recipient.clone(), proposal.amount));
+		Ok(())
+	}
+}
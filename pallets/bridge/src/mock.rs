@@ -0,0 +1,166 @@
+//! Test utilities
+
+use crate::{ChainId, Module, ResourceId, Trait};
+use frame_support::{impl_outer_event, impl_outer_origin, parameter_types, weights::Weight};
+use frame_system::EnsureRoot;
+use sp_core::H256;
+use sp_runtime::{
+	Perbill,
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+};
+use std::cell::RefCell;
+use zenlink_assets::AssetInfo;
+
+pub type AccountId = u64;
+pub type AssetId = u32;
+pub type TokenBalance = u64;
+pub type BlockNumber = u64;
+
+pub const RELAYER_A: AccountId = 1;
+pub const RELAYER_B: AccountId = 2;
+pub const RELAYER_C: AccountId = 3;
+pub const RECIPIENT: AccountId = 4;
+pub const OUTSIDER: AccountId = 5;
+
+/// The id of this chain.
+pub const THIS_CHAIN: ChainId = 1;
+/// The id of the chain deposits are made on.
+pub const SOURCE_CHAIN: ChainId = 2;
+/// The resource mapped to the asset issued by `new_test_ext`.
+pub const RESOURCE: ResourceId = [1; 32];
+
+impl_outer_origin! {
+	pub enum Origin for Test {}
+}
+
+mod bridge {
+	pub use crate::Event;
+}
+
+impl_outer_event! {
+	pub enum TestEvent for Test {
+		frame_system<T>,
+		zenlink_assets<T>,
+		bridge<T>,
+	}
+}
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct Test;
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const MaximumBlockWeight: Weight = 1024;
+	pub const MaximumBlockLength: u32 = 2 * 1024;
+	pub const AvailableBlockRatio: Perbill = Perbill::one();
+}
+
+impl frame_system::Trait for Test {
+	type BaseCallFilter = ();
+	type Origin = Origin;
+	type Call = ();
+	type Index = u64;
+	type BlockNumber = BlockNumber;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = TestEvent;
+	type BlockHashCount = BlockHashCount;
+	type MaximumBlockWeight = MaximumBlockWeight;
+	type DbWeight = ();
+	type BlockExecutionWeight = ();
+	type ExtrinsicBaseWeight = ();
+	type MaximumExtrinsicWeight = MaximumBlockWeight;
+	type MaximumBlockLength = MaximumBlockLength;
+	type AvailableBlockRatio = AvailableBlockRatio;
+	type Version = ();
+	type PalletInfo = ();
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+}
+
+impl zenlink_assets::Trait for Test {
+	type Event = TestEvent;
+	type TokenBalance = TokenBalance;
+	type AssetId = AssetId;
+}
+
+thread_local! {
+	static FROZEN: RefCell<Vec<AssetId>> = RefCell::new(Vec::new());
+}
+
+/// Allows minting the assets not passed to `freeze`.
+pub struct MockReserveGuard;
+
+impl pallet_reserves::ReserveGuard<AssetId> for MockReserveGuard {
+	fn can_mint(asset_id: &AssetId) -> bool {
+		FROZEN.with(|frozen| !frozen.borrow().contains(asset_id))
+	}
+}
+
+/// Freeze or unfreeze minting `asset_id`.
+pub fn set_frozen(asset_id: AssetId, frozen: bool) {
+	FROZEN.with(|assets| {
+		let mut assets = assets.borrow_mut();
+		assets.retain(|id| *id != asset_id);
+		if frozen {
+			assets.push(asset_id);
+		}
+	});
+}
+
+parameter_types! {
+	pub const BridgeChainId: ChainId = THIS_CHAIN;
+	pub const ProposalLifetime: BlockNumber = 10;
+}
+
+impl Trait for Test {
+	type Event = TestEvent;
+	type AdminOrigin = EnsureRoot<AccountId>;
+	type ChainId = BridgeChainId;
+	type ProposalLifetime = ProposalLifetime;
+	type ReserveGuard = MockReserveGuard;
+}
+
+pub type System = frame_system::Module<Test>;
+pub type Assets = zenlink_assets::Module<Test>;
+pub type Bridge = Module<Test>;
+
+/// Issue a new asset with `supply` owned by `owner`.
+pub fn issue(owner: AccountId, supply: TokenBalance) -> AssetId {
+	let info = AssetInfo { name: *b"wrapped token\0\0\0", symbol: *b"WTKN\0\0\0\0", decimals: 0 };
+	Assets::inner_issue(&owner, supply, &info)
+}
+
+/// The balance of `who` in `asset_id`.
+pub fn balance(asset_id: AssetId, who: AccountId) -> TokenBalance {
+	Assets::balance_of(&asset_id, &who)
+}
+
+/// Externalities with three relayers, a threshold of two, `SOURCE_CHAIN` whitelisted and
+/// `RESOURCE` mapped to a new asset, whose id is returned.
+pub fn new_test_ext() -> (sp_io::TestExternalities, AssetId) {
+	FROZEN.with(|frozen| frozen.borrow_mut().clear());
+
+	let storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	let mut ext = sp_io::TestExternalities::new(storage);
+	let asset_id = ext.execute_with(|| {
+		System::set_block_number(1);
+
+		for relayer in &[RELAYER_A, RELAYER_B, RELAYER_C] {
+			Bridge::add_relayer(Origin::root(), *relayer).unwrap();
+		}
+		Bridge::set_threshold(Origin::root(), 2).unwrap();
+		Bridge::whitelist_chain(Origin::root(), SOURCE_CHAIN).unwrap();
+
+		let asset_id = issue(OUTSIDER, 1_000);
+		Bridge::set_resource(Origin::root(), RESOURCE, asset_id).unwrap();
+		asset_id
+	});
+	(ext, asset_id)
+}
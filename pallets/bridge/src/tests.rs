@@ -0,0 +1,360 @@
+//! Tests for the bridge module.
+
+use crate::{mock::*, Error, ExcessPolicy, ProposalStatus, RateLimit, RawEvent};
+use frame_support::{assert_noop, assert_ok, dispatch::DispatchResult};
+use sp_core::H160;
+
+const NONCE: u64 = 1;
+const AMOUNT: TokenBalance = 100;
+
+fn acknowledge(relayer: AccountId) -> DispatchResult {
+	Bridge::acknowledge_proposal(Origin::signed(relayer), NONCE, SOURCE_CHAIN, RESOURCE, RECIPIENT, AMOUNT)
+}
+
+fn acknowledge_amount(relayer: AccountId, amount: TokenBalance) -> DispatchResult {
+	Bridge::acknowledge_proposal(Origin::signed(relayer), NONCE, SOURCE_CHAIN, RESOURCE, RECIPIENT, amount)
+}
+
+fn reject(relayer: AccountId) -> DispatchResult {
+	Bridge::reject_proposal(Origin::signed(relayer), NONCE, SOURCE_CHAIN, RESOURCE, RECIPIENT, AMOUNT)
+}
+
+fn status() -> Option<ProposalStatus> {
+	Bridge::votes(SOURCE_CHAIN, NONCE).map(|votes| votes.status)
+}
+
+/// Vote for minting `amount` of `resource_id` for deposit `nonce` with the threshold of relayers.
+fn approve(nonce: u64, resource_id: [u8; 32], amount: TokenBalance) -> DispatchResult {
+	for relayer in &[RELAYER_A, RELAYER_B] {
+		Bridge::acknowledge_proposal(Origin::signed(*relayer), nonce, SOURCE_CHAIN, resource_id, RECIPIENT, amount)?;
+	}
+	Ok(())
+}
+
+/// Limit inbound mints of `asset_id` to 150 per 10 blocks.
+fn limit_inbound(asset_id: AssetId, policy: ExcessPolicy) {
+	assert_ok!(Bridge::set_inbound_limit(
+		Origin::root(),
+		asset_id,
+		Some((RateLimit { amount: 150, period: 10 }, policy)),
+	));
+}
+
+fn has_event(event: RawEvent<AccountId, AssetId, TokenBalance, BlockNumber>) -> bool {
+	System::events().iter().any(|record| record.event == TestEvent::bridge(event.clone()))
+}
+
+#[test]
+fn set_resource_requires_an_existing_asset() {
+	let (mut ext, asset_id) = new_test_ext();
+	ext.execute_with(|| {
+		let unknown = asset_id + 1;
+
+		assert_noop!(Bridge::set_resource(Origin::root(), [2; 32], unknown), Error::<Test>::AssetNotFound);
+		assert_eq!(Bridge::resource(RESOURCE), Some(asset_id));
+	});
+}
+
+#[test]
+fn threshold_votes_approve_and_mint() {
+	let (mut ext, asset_id) = new_test_ext();
+	ext.execute_with(|| {
+		assert_ok!(acknowledge(RELAYER_A));
+		assert_eq!(status(), Some(ProposalStatus::Initiated));
+		assert_eq!(balance(asset_id, RECIPIENT), 0);
+
+		assert_ok!(acknowledge(RELAYER_B));
+		assert_eq!(status(), Some(ProposalStatus::Executed));
+		assert_eq!(balance(asset_id, RECIPIENT), AMOUNT);
+
+		assert_noop!(acknowledge(RELAYER_C), Error::<Test>::ProposalAlreadyComplete);
+	});
+}
+
+#[test]
+fn proposal_is_rejected_once_the_threshold_can_not_be_reached() {
+	let (mut ext, asset_id) = new_test_ext();
+	ext.execute_with(|| {
+		// With three relayers and a threshold of two, one vote against still leaves two in
+		// favour possible.
+		assert_ok!(reject(RELAYER_A));
+		assert_eq!(status(), Some(ProposalStatus::Initiated));
+
+		assert_ok!(acknowledge(RELAYER_B));
+		assert_eq!(status(), Some(ProposalStatus::Initiated));
+
+		assert_ok!(reject(RELAYER_C));
+		assert_eq!(status(), Some(ProposalStatus::Rejected));
+		assert_eq!(balance(asset_id, RECIPIENT), 0);
+	});
+}
+
+#[test]
+fn only_relayers_can_vote() {
+	let (mut ext, _) = new_test_ext();
+	ext.execute_with(|| {
+		assert_noop!(acknowledge(OUTSIDER), Error::<Test>::MustBeRelayer);
+		assert_noop!(reject(OUTSIDER), Error::<Test>::MustBeRelayer);
+
+		assert_ok!(Bridge::remove_relayer(Origin::root(), RELAYER_A));
+		assert_noop!(acknowledge(RELAYER_A), Error::<Test>::MustBeRelayer);
+	});
+}
+
+#[test]
+fn relayers_can_vote_only_once() {
+	let (mut ext, _) = new_test_ext();
+	ext.execute_with(|| {
+		assert_ok!(acknowledge(RELAYER_A));
+
+		assert_noop!(acknowledge(RELAYER_A), Error::<Test>::RelayerAlreadyVoted);
+		assert_noop!(reject(RELAYER_A), Error::<Test>::RelayerAlreadyVoted);
+	});
+}
+
+#[test]
+fn votes_must_match_the_proposal() {
+	let (mut ext, _) = new_test_ext();
+	ext.execute_with(|| {
+		assert_ok!(acknowledge(RELAYER_A));
+
+		assert_noop!(
+			Bridge::acknowledge_proposal(
+				Origin::signed(RELAYER_B),
+				NONCE,
+				SOURCE_CHAIN,
+				RESOURCE,
+				RECIPIENT,
+				AMOUNT + 1,
+			),
+			Error::<Test>::ProposalMismatch
+		);
+	});
+}
+
+#[test]
+fn proposals_expire_after_their_lifetime() {
+	let (mut ext, asset_id) = new_test_ext();
+	ext.execute_with(|| {
+		assert_ok!(acknowledge(RELAYER_A));
+
+		// Created in block 1 with a lifetime of 10 blocks.
+		System::set_block_number(11);
+		assert_ok!(reject(RELAYER_C));
+
+		System::set_block_number(12);
+		assert_noop!(acknowledge(RELAYER_B), Error::<Test>::ProposalExpired);
+		assert_eq!(balance(asset_id, RECIPIENT), 0);
+	});
+}
+
+#[test]
+fn votes_on_unknown_resources_or_chains_fail() {
+	let (mut ext, _) = new_test_ext();
+	ext.execute_with(|| {
+		assert_noop!(
+			Bridge::acknowledge_proposal(Origin::signed(RELAYER_A), NONCE, SOURCE_CHAIN, [2; 32], RECIPIENT, AMOUNT),
+			Error::<Test>::ResourceDoesNotExist
+		);
+		assert_noop!(
+			Bridge::acknowledge_proposal(Origin::signed(RELAYER_A), NONCE, 3, RESOURCE, RECIPIENT, AMOUNT),
+			Error::<Test>::ChainNotWhitelisted
+		);
+
+		assert_ok!(Bridge::remove_resource(Origin::root(), RESOURCE));
+		assert_noop!(acknowledge(RELAYER_A), Error::<Test>::ResourceDoesNotExist);
+		assert_noop!(Bridge::remove_resource(Origin::root(), RESOURCE), Error::<Test>::ResourceDoesNotExist);
+	});
+}
+
+#[test]
+fn transfer_out_burns_and_emits_a_transfer() {
+	let (mut ext, asset_id) = new_test_ext();
+	ext.execute_with(|| {
+		let recipient = H160::repeat_byte(1);
+
+		assert_ok!(Bridge::transfer_out(Origin::signed(OUTSIDER), RESOURCE, AMOUNT, recipient, SOURCE_CHAIN));
+		assert_ok!(Bridge::transfer_out(Origin::signed(OUTSIDER), RESOURCE, AMOUNT, recipient, SOURCE_CHAIN));
+
+		assert_eq!(balance(asset_id, OUTSIDER), 1_000 - 2 * AMOUNT);
+		assert_eq!(Bridge::chain_nonce(SOURCE_CHAIN), Some(2));
+		assert!(has_event(RawEvent::FungibleTransfer(SOURCE_CHAIN, 1, RESOURCE, AMOUNT, recipient)));
+		assert!(has_event(RawEvent::FungibleTransfer(SOURCE_CHAIN, 2, RESOURCE, AMOUNT, recipient)));
+	});
+}
+
+#[test]
+fn transfer_out_requires_an_amount_a_chain_and_a_resource() {
+	let (mut ext, _) = new_test_ext();
+	ext.execute_with(|| {
+		let recipient = H160::repeat_byte(1);
+
+		assert_noop!(
+			Bridge::transfer_out(Origin::signed(OUTSIDER), RESOURCE, 0, recipient, SOURCE_CHAIN),
+			Error::<Test>::ZeroAmount
+		);
+		assert_noop!(
+			Bridge::transfer_out(Origin::signed(OUTSIDER), RESOURCE, AMOUNT, recipient, 3),
+			Error::<Test>::ChainNotWhitelisted
+		);
+		assert_noop!(
+			Bridge::transfer_out(Origin::signed(OUTSIDER), [2; 32], AMOUNT, recipient, SOURCE_CHAIN),
+			Error::<Test>::ResourceDoesNotExist
+		);
+	});
+}
+
+#[test]
+fn outbound_limit_applies_per_window() {
+	let (mut ext, asset_id) = new_test_ext();
+	ext.execute_with(|| {
+		let recipient = H160::repeat_byte(1);
+		assert_ok!(Bridge::set_outbound_limit(Origin::root(), RESOURCE, Some(RateLimit { amount: 150, period: 10 })));
+
+		assert_ok!(Bridge::transfer_out(Origin::signed(OUTSIDER), RESOURCE, 100, recipient, SOURCE_CHAIN));
+		assert_noop!(
+			Bridge::transfer_out(Origin::signed(OUTSIDER), RESOURCE, 51, recipient, SOURCE_CHAIN),
+			Error::<Test>::RateLimitExceeded
+		);
+		assert_ok!(Bridge::transfer_out(Origin::signed(OUTSIDER), RESOURCE, 50, recipient, SOURCE_CHAIN));
+
+		// The window is 10 blocks long.
+		System::set_block_number(11);
+		assert_ok!(Bridge::transfer_out(Origin::signed(OUTSIDER), RESOURCE, 150, recipient, SOURCE_CHAIN));
+		assert_eq!(balance(asset_id, OUTSIDER), 1_000 - 300);
+	});
+}
+
+#[test]
+fn deferred_proposal_is_executed_once_the_window_has_room() {
+	let (mut ext, asset_id) = new_test_ext();
+	ext.execute_with(|| {
+		limit_inbound(asset_id, ExcessPolicy::Defer);
+
+		assert_ok!(approve(1, RESOURCE, 100));
+		assert_eq!(Bridge::votes(SOURCE_CHAIN, 1).unwrap().status, ProposalStatus::Executed);
+
+		assert_ok!(approve(2, RESOURCE, 100));
+		assert_eq!(Bridge::votes(SOURCE_CHAIN, 2).unwrap().status, ProposalStatus::Approved);
+		assert_eq!(balance(asset_id, RECIPIENT), 100);
+		assert_noop!(Bridge::execute_proposal(Origin::signed(OUTSIDER), 2, SOURCE_CHAIN), Error::<Test>::RateLimitExceeded);
+
+		System::set_block_number(11);
+		assert_ok!(Bridge::execute_proposal(Origin::signed(OUTSIDER), 2, SOURCE_CHAIN));
+		assert_eq!(Bridge::votes(SOURCE_CHAIN, 2).unwrap().status, ProposalStatus::Executed);
+		assert_eq!(balance(asset_id, RECIPIENT), 200);
+		assert!(has_event(RawEvent::ProposalExecuted(SOURCE_CHAIN, 2)));
+	});
+}
+
+#[test]
+fn execute_proposal_requires_an_approved_proposal() {
+	let (mut ext, _) = new_test_ext();
+	ext.execute_with(|| {
+		assert_noop!(
+			Bridge::execute_proposal(Origin::signed(OUTSIDER), NONCE, SOURCE_CHAIN),
+			Error::<Test>::ProposalDoesNotExist
+		);
+
+		assert_ok!(acknowledge(RELAYER_A));
+		assert_noop!(
+			Bridge::execute_proposal(Origin::signed(OUTSIDER), NONCE, SOURCE_CHAIN),
+			Error::<Test>::ProposalNotApproved
+		);
+
+		assert_ok!(acknowledge(RELAYER_B));
+		assert_noop!(
+			Bridge::execute_proposal(Origin::signed(OUTSIDER), NONCE, SOURCE_CHAIN),
+			Error::<Test>::ProposalNotApproved
+		);
+	});
+}
+
+#[test]
+fn mints_larger_than_a_window_are_refused() {
+	let (mut ext, asset_id) = new_test_ext();
+	ext.execute_with(|| {
+		limit_inbound(asset_id, ExcessPolicy::Queue);
+
+		assert_ok!(acknowledge_amount(RELAYER_A, 151));
+		assert_noop!(acknowledge_amount(RELAYER_B, 151), Error::<Test>::MintExceedsLimit);
+		assert_eq!(status(), Some(ProposalStatus::Initiated));
+		assert_eq!(Bridge::mint_queue(asset_id), (0, 0));
+	});
+}
+
+#[test]
+fn queued_mints_are_executed_in_order_once_the_window_has_room() {
+	let (mut ext, asset_id) = new_test_ext();
+	ext.execute_with(|| {
+		limit_inbound(asset_id, ExcessPolicy::Queue);
+
+		assert_ok!(approve(1, RESOURCE, 100));
+		assert_ok!(approve(2, RESOURCE, 100));
+		assert_ok!(approve(3, RESOURCE, 50));
+		assert_eq!(Bridge::votes(SOURCE_CHAIN, 2).unwrap().status, ProposalStatus::Executed);
+		assert!(has_event(RawEvent::MintQueued(asset_id, 0, RECIPIENT, 100)));
+		assert!(has_event(RawEvent::MintQueued(asset_id, 1, RECIPIENT, 50)));
+		assert_eq!(balance(asset_id, RECIPIENT), 100);
+
+		assert_noop!(Bridge::execute_queued_mint(Origin::signed(OUTSIDER), asset_id), Error::<Test>::RateLimitExceeded);
+
+		System::set_block_number(11);
+		assert_ok!(Bridge::execute_queued_mint(Origin::signed(OUTSIDER), asset_id));
+		assert_ok!(Bridge::execute_queued_mint(Origin::signed(OUTSIDER), asset_id));
+		assert_eq!(balance(asset_id, RECIPIENT), 250);
+		assert!(has_event(RawEvent::QueuedMintExecuted(asset_id, 0)));
+		assert!(has_event(RawEvent::QueuedMintExecuted(asset_id, 1)));
+
+		assert_noop!(Bridge::execute_queued_mint(Origin::signed(OUTSIDER), asset_id), Error::<Test>::QueueEmpty);
+	});
+}
+
+#[test]
+fn a_frozen_asset_only_holds_up_its_own_queue() {
+	let (mut ext, asset_id) = new_test_ext();
+	ext.execute_with(|| {
+		let other_resource = [3; 32];
+		let other_asset = issue(OUTSIDER, 1_000);
+		assert_ok!(Bridge::set_resource(Origin::root(), other_resource, other_asset));
+		limit_inbound(asset_id, ExcessPolicy::Queue);
+		limit_inbound(other_asset, ExcessPolicy::Queue);
+
+		assert_ok!(approve(1, RESOURCE, 150));
+		assert_ok!(approve(2, RESOURCE, 100));
+		assert_ok!(approve(3, other_resource, 150));
+		assert_ok!(approve(4, other_resource, 100));
+
+		System::set_block_number(11);
+		set_frozen(asset_id, true);
+		assert_noop!(Bridge::execute_queued_mint(Origin::signed(OUTSIDER), asset_id), Error::<Test>::MintingFrozen);
+		assert_ok!(Bridge::execute_queued_mint(Origin::signed(OUTSIDER), other_asset));
+		assert_eq!(balance(other_asset, RECIPIENT), 250);
+
+		set_frozen(asset_id, false);
+		assert_ok!(Bridge::execute_queued_mint(Origin::signed(OUTSIDER), asset_id));
+		assert_eq!(balance(asset_id, RECIPIENT), 250);
+	});
+}
+
+#[test]
+fn frozen_minting_defers_approved_proposals() {
+	let (mut ext, asset_id) = new_test_ext();
+	ext.execute_with(|| {
+		set_frozen(asset_id, true);
+
+		assert_ok!(acknowledge(RELAYER_A));
+		assert_ok!(acknowledge(RELAYER_B));
+		assert_eq!(status(), Some(ProposalStatus::Approved));
+		assert_eq!(balance(asset_id, RECIPIENT), 0);
+		assert_noop!(
+			Bridge::execute_proposal(Origin::signed(OUTSIDER), NONCE, SOURCE_CHAIN),
+			Error::<Test>::MintingFrozen
+		);
+
+		set_frozen(asset_id, false);
+		assert_ok!(Bridge::execute_proposal(Origin::signed(OUTSIDER), NONCE, SOURCE_CHAIN));
+		assert_eq!(status(), Some(ProposalStatus::Executed));
+		assert_eq!(balance(asset_id, RECIPIENT), AMOUNT);
+	});
+}
@@ -0,0 +1,275 @@
+//! # Forwarder Module
+//!
+//! Gasless meta-transactions. A user signs a [`ForwardRequest`] off-chain and hands it to a
+//! relayer, who submits it with `forward` and pays the native transaction fee. The wrapped call
+//! is dispatched with the user as signed origin.
+//!
+//! A request can carry a fee which is paid from the user to the relayer through
+//! [`Trait::RelayerFee`], so users holding only assets can still reimburse relayers.
+//!
+//! Requests are replay protected by a per-account nonce kept by this module, a validity window
+//! in blocks, and the genesis hash included in the signed payload.
+//!
+//! Only registered relayers can forward requests. Relayers reserve [`Trait::RelayerBond`] with
+//! `register_relayer` and advertise the maximum fees they charge; a request whose fee exceeds
+//! all of them is refused, so users are not overcharged by the relayer submitting their
+//! request.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode};
+use frame_support::{
+	decl_error, decl_event, decl_module, decl_storage,
+	dispatch::{DispatchResult, Parameter},
+	ensure,
+	traits::{Currency, Get, ReservableCurrency},
+	weights::{GetDispatchInfo, PostDispatchInfo},
+};
+use frame_system::ensure_signed;
+use sp_runtime::{
+	RuntimeDebug,
+	traits::{Dispatchable, IdentifyAccount, Verify, Zero},
+};
+use sp_std::prelude::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+/// Context prefixed to every signed payload so forward signatures can not be reused elsewhere.
+pub const FORWARD_CONTEXT: &[u8] = b"canvas/forward";
+
+/// Charges the fee a user agreed to pay to the relayer submitting their request.
+pub trait RelayerFee<AccountId> {
+	/// The fee as described in a request, e.g. an asset and an amount.
+	type Fee: Parameter;
+
+	/// Move `fee` from `who` to `relayer`.
+	fn pay(who: &AccountId, relayer: &AccountId, fee: &Self::Fee) -> DispatchResult;
+
+	/// Whether `fee` is at most `max`, e.g. the same asset and no larger an amount.
+	fn within(fee: &Self::Fee, max: &Self::Fee) -> bool;
+}
+
+/// Relayers are not paid by the users.
+impl<AccountId> RelayerFee<AccountId> for () {
+	type Fee = ();
+
+	fn pay(_: &AccountId, _: &AccountId, _: &()) -> DispatchResult {
+		Ok(())
+	}
+
+	fn within(_: &(), _: &()) -> bool {
+		true
+	}
+}
+
+/// A call signed by `signer` to be submitted by a relayer.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct ForwardRequest<Call, AccountId, BlockNumber, Fee> {
+	/// The call to dispatch on behalf of `signer`.
+	pub call: Box<Call>,
+	/// The account signing the request.
+	pub signer: AccountId,
+	/// Must equal the signer's current forwarder nonce.
+	pub nonce: u32,
+	/// The first block the request can be included in.
+	pub valid_from: BlockNumber,
+	/// The last block the request can be included in.
+	pub valid_until: BlockNumber,
+	/// The fee paid to the relayer, if any.
+	pub fee: Option<Fee>,
+}
+
+pub type BalanceOf<T> =
+	<<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
+
+pub type FeeOf<T> =
+	<<T as Trait>::RelayerFee as RelayerFee<<T as frame_system::Trait>::AccountId>>::Fee;
+
+pub type ForwardRequestOf<T> = ForwardRequest<
+	<T as Trait>::Call,
+	<T as frame_system::Trait>::AccountId,
+	<T as frame_system::Trait>::BlockNumber,
+	FeeOf<T>,
+>;
+
+pub trait Trait: frame_system::Trait {
+	/// The overarching event type.
+	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+
+	/// The calls which can be forwarded.
+	type Call: Parameter
+		+ Dispatchable<Origin = Self::Origin, PostInfo = PostDispatchInfo>
+		+ GetDispatchInfo;
+
+	/// The signature users sign their requests with.
+	type Signature: Parameter + Verify<Signer = Self::Signer>;
+
+	/// The public key behind `Signature`, identifying an account.
+	type Signer: IdentifyAccount<AccountId = Self::AccountId>;
+
+	/// How the fee of a request is paid to the relayer.
+	type RelayerFee: RelayerFee<Self::AccountId>;
+
+	/// The currency the relayer bond is reserved in.
+	type Currency: ReservableCurrency<Self::AccountId>;
+
+	/// The amount reserved from a relayer while registered.
+	type RelayerBond: Get<BalanceOf<Self>>;
+
+	/// The maximum number of fees a relayer can advertise.
+	type MaxRelayerFees: Get<u32>;
+}
+
+decl_storage! {
+	trait Store for Module<T: Trait> as Forwarder {
+		/// The nonce the next request of an account must use.
+		pub Nonces get(fn nonce): map hasher(blake2_128_concat) T::AccountId => u32;
+
+		/// The registered relayers and the maximum fees they charge.
+		pub Relayers get(fn relayer_fees): map hasher(blake2_128_concat) T::AccountId => Option<Vec<FeeOf<T>>>;
+
+		/// The bond reserved from a registered relayer.
+		pub Bonds get(fn relayer_bond): map hasher(blake2_128_concat) T::AccountId => BalanceOf<T>;
+	}
+}
+
+decl_event! {
+	pub enum Event<T> where AccountId = <T as frame_system::Trait>::AccountId {
+		/// A request was forwarded and dispatched. \[relayer, signer, nonce, result\]
+		Forwarded(AccountId, AccountId, u32, DispatchResult),
+		/// A relayer registered or updated its fees. \[relayer\]
+		RelayerRegistered(AccountId),
+		/// A relayer unregistered and its bond was returned. \[relayer\]
+		RelayerUnregistered(AccountId),
+	}
+}
+
+decl_error! {
+	pub enum Error for Module<T: Trait> {
+		/// The request's validity window has not started yet.
+		NotYetValid,
+		/// The request's validity window has passed.
+		Expired,
+		/// The request does not use the signer's current nonce.
+		BadNonce,
+		/// The signature does not match the request and signer.
+		BadSignature,
+		/// The origin is not a registered relayer.
+		NotRelayer,
+		/// The request's fee exceeds the fees the relayer advertised.
+		FeeAboveMax,
+		/// More fees than `MaxRelayerFees` were advertised.
+		TooManyFees,
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
+		/// The amount reserved from a relayer while registered.
+		const RelayerBond: BalanceOf<T> = T::RelayerBond::get();
+
+		/// The maximum number of fees a relayer can advertise.
+		const MaxRelayerFees: u32 = T::MaxRelayerFees::get();
+
+		fn deposit_event() = default;
+
+		/// Dispatch a call signed off-chain by `request.signer`.
+		///
+		/// The origin is a registered relayer and pays the transaction fee. The request's own
+		/// fee, if any, must be within one of the relayer's advertised fees and is paid to the
+		/// relayer before the call is dispatched. The signer's nonce is used up
+		/// even if the wrapped call fails; its result is reported in the `Forwarded` event.
+		#[weight = {
+			let dispatch_info = request.call.get_dispatch_info();
+			(
+				dispatch_info.weight
+					.saturating_add(50_000_000)
+					.saturating_add(T::DbWeight::get().reads_writes(4, 1)),
+				dispatch_info.class,
+			)
+		}]
+		pub fn forward(origin, request: ForwardRequestOf<T>, signature: T::Signature) {
+			let relayer = ensure_signed(origin)?;
+			let max_fees = Self::relayer_fees(&relayer).ok_or(Error::<T>::NotRelayer)?;
+
+			Self::validate(&request, &signature)?;
+
+			if let Some(fee) = &request.fee {
+				ensure!(
+					max_fees.iter().any(|max| T::RelayerFee::within(fee, max)),
+					Error::<T>::FeeAboveMax
+				);
+				T::RelayerFee::pay(&request.signer, &relayer, fee)?;
+			}
+			Nonces::<T>::insert(&request.signer, request.nonce.saturating_add(1));
+
+			let ForwardRequest { call, signer, nonce, .. } = request;
+			let result = call.dispatch(frame_system::RawOrigin::Signed(signer.clone()).into());
+
+			Self::deposit_event(RawEvent::Forwarded(
+				relayer,
+				signer,
+				nonce,
+				result.map(|_| ()).map_err(|e| e.error),
+			));
+		}
+
+		/// Register the origin as a relayer charging at most `max_fees`, or update the fees of a
+		/// registered relayer.
+		///
+		/// `RelayerBond` is reserved on first registration. The reserved amount is returned on
+		/// unregistration, even if `RelayerBond` changed in between.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(2, 3)]
+		pub fn register_relayer(origin, max_fees: Vec<FeeOf<T>>) {
+			let who = ensure_signed(origin)?;
+			ensure!(max_fees.len() <= T::MaxRelayerFees::get() as usize, Error::<T>::TooManyFees);
+
+			if !Relayers::<T>::contains_key(&who) {
+				let bond = T::RelayerBond::get();
+				T::Currency::reserve(&who, bond)?;
+				Bonds::<T>::insert(&who, bond);
+			}
+			Relayers::<T>::insert(&who, max_fees);
+
+			Self::deposit_event(RawEvent::RelayerRegistered(who));
+		}
+
+		/// Unregister the origin as a relayer and return its bond.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(3, 3)]
+		pub fn unregister_relayer(origin) {
+			let who = ensure_signed(origin)?;
+			ensure!(Relayers::<T>::contains_key(&who), Error::<T>::NotRelayer);
+
+			Relayers::<T>::remove(&who);
+			T::Currency::unreserve(&who, Bonds::<T>::take(&who));
+
+			Self::deposit_event(RawEvent::RelayerUnregistered(who));
+		}
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// The bytes a user has to sign for `request`.
+	pub fn signing_payload(request: &ForwardRequestOf<T>) -> Vec<u8> {
+		let genesis_hash = frame_system::Module::<T>::block_hash(T::BlockNumber::zero());
+		(FORWARD_CONTEXT, genesis_hash, request).encode()
+	}
+
+	fn validate(request: &ForwardRequestOf<T>, signature: &T::Signature) -> DispatchResult {
+		let now = frame_system::Module::<T>::block_number();
+		ensure!(now >= request.valid_from, Error::<T>::NotYetValid);
+		ensure!(now <= request.valid_until, Error::<T>::Expired);
+		ensure!(request.nonce == Self::nonce(&request.signer), Error::<T>::BadNonce);
+
+		let payload = Self::signing_payload(request);
+		ensure!(signature.verify(&payload[..], &request.signer), Error::<T>::BadSignature);
+
+		Ok(())
+	}
+}
@@ -0,0 +1,148 @@
+//! Test utilities
+
+use crate::{Module, RelayerFee, Trait};
+use frame_support::{
+	dispatch::DispatchResult,
+	impl_outer_dispatch, impl_outer_event, impl_outer_origin, parameter_types,
+	traits::{Currency, ExistenceRequirement},
+	weights::Weight,
+};
+use sp_core::H256;
+use sp_runtime::{
+	Perbill,
+	testing::{Header, TestSignature, UintAuthorityId},
+	traits::{BlakeTwo256, IdentityLookup},
+};
+
+pub type AccountId = u64;
+pub type Balance = u64;
+pub type BlockNumber = u64;
+
+pub const ALICE: AccountId = 1;
+pub const RELAYER: AccountId = 2;
+
+/// The bond reserved from a relayer.
+pub const BOND: Balance = 100;
+
+impl_outer_origin! {
+	pub enum Origin for Test {}
+}
+
+impl_outer_dispatch! {
+	pub enum Call for Test where origin: Origin {
+		frame_system::System,
+	}
+}
+
+mod forwarder {
+	pub use crate::Event;
+}
+
+impl_outer_event! {
+	pub enum TestEvent for Test {
+		frame_system<T>,
+		pallet_balances<T>,
+		forwarder<T>,
+	}
+}
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct Test;
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const MaximumBlockWeight: Weight = 1024;
+	pub const MaximumBlockLength: u32 = 2 * 1024;
+	pub const AvailableBlockRatio: Perbill = Perbill::one();
+}
+
+impl frame_system::Trait for Test {
+	type BaseCallFilter = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = BlockNumber;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = TestEvent;
+	type BlockHashCount = BlockHashCount;
+	type MaximumBlockWeight = MaximumBlockWeight;
+	type DbWeight = ();
+	type BlockExecutionWeight = ();
+	type ExtrinsicBaseWeight = ();
+	type MaximumExtrinsicWeight = MaximumBlockWeight;
+	type MaximumBlockLength = MaximumBlockLength;
+	type AvailableBlockRatio = AvailableBlockRatio;
+	type Version = ();
+	type PalletInfo = ();
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: Balance = 1;
+}
+
+impl pallet_balances::Trait for Test {
+	type Balance = Balance;
+	type DustRemoval = ();
+	type Event = TestEvent;
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = frame_system::Module<Test>;
+	type MaxLocks = ();
+	type WeightInfo = ();
+}
+
+/// Pays relayers in the native currency.
+pub struct NativeFee;
+
+impl RelayerFee<AccountId> for NativeFee {
+	type Fee = Balance;
+
+	fn pay(who: &AccountId, relayer: &AccountId, fee: &Balance) -> DispatchResult {
+		<Balances as Currency<_>>::transfer(who, relayer, *fee, ExistenceRequirement::KeepAlive)
+	}
+
+	fn within(fee: &Balance, max: &Balance) -> bool {
+		fee <= max
+	}
+}
+
+parameter_types! {
+	pub const RelayerBond: Balance = BOND;
+	pub const MaxRelayerFees: u32 = 2;
+}
+
+impl Trait for Test {
+	type Event = TestEvent;
+	type Call = Call;
+	type Signature = TestSignature;
+	type Signer = UintAuthorityId;
+	type RelayerFee = NativeFee;
+	type Currency = Balances;
+	type RelayerBond = RelayerBond;
+	type MaxRelayerFees = MaxRelayerFees;
+}
+
+pub type System = frame_system::Module<Test>;
+pub type Balances = pallet_balances::Module<Test>;
+pub type Forwarder = Module<Test>;
+
+/// Externalities in which every account holds 1_000 of the native currency.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(ALICE, 1_000), (RELAYER, 1_000)],
+	}
+	.assimilate_storage(&mut storage)
+	.unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(storage);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,188 @@
+//! Tests for the forwarder module.
+
+use crate::{mock::*, Error, ForwardRequest, ForwardRequestOf, RawEvent, FORWARD_CONTEXT};
+use codec::Encode;
+use frame_support::{assert_noop, assert_ok};
+use sp_core::H256;
+use sp_runtime::{DispatchError, DispatchResult, testing::TestSignature};
+
+fn remark() -> Call {
+	Call::System(frame_system::Call::remark(vec![]))
+}
+
+/// A request of Alice valid from block 1 to block 10.
+fn request(call: Call, nonce: u32, fee: Option<Balance>) -> ForwardRequestOf<Test> {
+	ForwardRequest {
+		call: Box::new(call),
+		signer: ALICE,
+		nonce,
+		valid_from: 1,
+		valid_until: 10,
+		fee,
+	}
+}
+
+fn sign(request: &ForwardRequestOf<Test>) -> TestSignature {
+	TestSignature(request.signer, Forwarder::signing_payload(request))
+}
+
+/// Register the relayer charging at most 5.
+fn register() {
+	assert_ok!(Forwarder::register_relayer(Origin::signed(RELAYER), vec![5]));
+}
+
+fn forwarded(nonce: u32, result: DispatchResult) -> bool {
+	let event = TestEvent::forwarder(RawEvent::Forwarded(RELAYER, ALICE, nonce, result));
+	System::events().iter().any(|record| record.event == event)
+}
+
+#[test]
+fn forward_dispatches_as_the_signer_and_pays_the_fee() {
+	new_test_ext().execute_with(|| {
+		register();
+		let request = request(remark(), 0, Some(3));
+
+		assert_ok!(Forwarder::forward(Origin::signed(RELAYER), request.clone(), sign(&request)));
+
+		assert_eq!(Forwarder::nonce(ALICE), 1);
+		assert_eq!(Balances::free_balance(ALICE), 997);
+		assert_eq!(Balances::free_balance(RELAYER), 1_000 - BOND + 3);
+		assert!(forwarded(0, Ok(())));
+	});
+}
+
+#[test]
+fn forward_requires_a_registered_relayer() {
+	new_test_ext().execute_with(|| {
+		let request = request(remark(), 0, None);
+
+		assert_noop!(
+			Forwarder::forward(Origin::signed(RELAYER), request.clone(), sign(&request)),
+			Error::<Test>::NotRelayer
+		);
+	});
+}
+
+#[test]
+fn replayed_request_is_refused() {
+	new_test_ext().execute_with(|| {
+		register();
+		let request = request(remark(), 0, Some(3));
+		assert_ok!(Forwarder::forward(Origin::signed(RELAYER), request.clone(), sign(&request)));
+
+		assert_noop!(
+			Forwarder::forward(Origin::signed(RELAYER), request.clone(), sign(&request)),
+			Error::<Test>::BadNonce
+		);
+		assert_eq!(Balances::free_balance(ALICE), 997);
+	});
+}
+
+#[test]
+fn request_outside_its_window_is_refused() {
+	new_test_ext().execute_with(|| {
+		register();
+		let mut early = request(remark(), 0, None);
+		early.valid_from = 2;
+		assert_noop!(
+			Forwarder::forward(Origin::signed(RELAYER), early.clone(), sign(&early)),
+			Error::<Test>::NotYetValid
+		);
+
+		System::set_block_number(11);
+		let expired = request(remark(), 0, None);
+		assert_noop!(
+			Forwarder::forward(Origin::signed(RELAYER), expired.clone(), sign(&expired)),
+			Error::<Test>::Expired
+		);
+	});
+}
+
+#[test]
+fn signature_of_another_account_is_refused() {
+	new_test_ext().execute_with(|| {
+		register();
+		let request = request(remark(), 0, None);
+		let signature = TestSignature(RELAYER, Forwarder::signing_payload(&request));
+
+		assert_noop!(
+			Forwarder::forward(Origin::signed(RELAYER), request, signature),
+			Error::<Test>::BadSignature
+		);
+	});
+}
+
+#[test]
+fn signature_for_another_chain_is_refused() {
+	new_test_ext().execute_with(|| {
+		register();
+		let request = request(remark(), 0, None);
+		let signature = TestSignature(ALICE, (FORWARD_CONTEXT, H256::repeat_byte(1), &request).encode());
+
+		assert_noop!(
+			Forwarder::forward(Origin::signed(RELAYER), request, signature),
+			Error::<Test>::BadSignature
+		);
+	});
+}
+
+#[test]
+fn fee_above_the_relayer_maximum_is_refused() {
+	new_test_ext().execute_with(|| {
+		register();
+		let request = request(remark(), 0, Some(6));
+
+		assert_noop!(
+			Forwarder::forward(Origin::signed(RELAYER), request.clone(), sign(&request)),
+			Error::<Test>::FeeAboveMax
+		);
+		assert_eq!(Balances::free_balance(ALICE), 1_000);
+	});
+}
+
+#[test]
+fn failing_call_uses_up_the_nonce() {
+	new_test_ext().execute_with(|| {
+		register();
+		// Setting the code needs the root origin, so the call fails for Alice.
+		let request = request(Call::System(frame_system::Call::set_code(vec![])), 0, Some(3));
+
+		assert_ok!(Forwarder::forward(Origin::signed(RELAYER), request.clone(), sign(&request)));
+
+		assert_eq!(Forwarder::nonce(ALICE), 1);
+		assert_eq!(Balances::free_balance(ALICE), 997);
+		assert!(forwarded(0, Err(DispatchError::BadOrigin)));
+	});
+}
+
+#[test]
+fn relayer_bond_is_reserved_once_and_returned() {
+	new_test_ext().execute_with(|| {
+		register();
+		assert_eq!(Balances::reserved_balance(RELAYER), BOND);
+		assert_eq!(Forwarder::relayer_bond(RELAYER), BOND);
+
+		// Updating the fees does not reserve the bond again.
+		assert_ok!(Forwarder::register_relayer(Origin::signed(RELAYER), vec![5, 10]));
+		assert_eq!(Balances::reserved_balance(RELAYER), BOND);
+		assert_eq!(Forwarder::relayer_fees(RELAYER), Some(vec![5, 10]));
+
+		assert_ok!(Forwarder::unregister_relayer(Origin::signed(RELAYER)));
+		assert_eq!(Balances::reserved_balance(RELAYER), 0);
+		assert_eq!(Balances::free_balance(RELAYER), 1_000);
+		assert_eq!(Forwarder::relayer_fees(RELAYER), None);
+		assert_eq!(Forwarder::relayer_bond(RELAYER), 0);
+
+		assert_noop!(Forwarder::unregister_relayer(Origin::signed(RELAYER)), Error::<Test>::NotRelayer);
+	});
+}
+
+#[test]
+fn register_relayer_limits_the_fees() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Forwarder::register_relayer(Origin::signed(RELAYER), vec![1, 2, 3]),
+			Error::<Test>::TooManyFees
+		);
+	});
+}
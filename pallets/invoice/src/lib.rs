@@ -0,0 +1,274 @@
+//! # Invoice Module
+//!
+//! Payment requests for point-of-sale integrations. A merchant creates an [`Invoice`] for an
+//! amount of a zenlink asset with `create_invoice`; any account can settle it before its expiry
+//! with `pay_invoice`, which transfers the amount to the merchant and marks the invoice paid in
+//! the same call. The memo is chosen by the merchant, e.g. an order reference, and is repeated in
+//! the events so payments can be correlated off-chain.
+//!
+//! A payer holding a different asset can pay with it instead. `pay_invoice` then swaps at most
+//! the amount the payer allows for exactly the invoice amount through the zenlink dex, with the
+//! merchant as the recipient of the swap. The swap and the invoice update happen in the same
+//! transactional call, so either both or neither take effect.
+//!
+//! Creating an invoice reserves [`Trait::InvoiceDeposit`] from the merchant for the storage it
+//! uses. Invoices are kept until the merchant removes them with `remove_invoice`, which returns
+//! the deposit.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode};
+use frame_support::{
+	decl_error, decl_event, decl_module, decl_storage, ensure,
+	traits::{Currency, Get, ReservableCurrency},
+	transactional,
+};
+use frame_system::{ensure_signed, RawOrigin};
+use sp_runtime::{
+	DispatchResult, RuntimeDebug,
+	traits::{Saturating, Zero},
+};
+use sp_std::prelude::*;
+use zenlink_dex::SwapHandler;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+pub type InvoiceId = u64;
+
+/// A request to pay an amount of an asset to a merchant.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Invoice<AccountId, AssetId, TokenBalance, BlockNumber, Balance> {
+	/// Receives the payment.
+	pub merchant: AccountId,
+	/// The asset to pay in.
+	pub asset_id: AssetId,
+	/// The amount to pay.
+	pub amount: TokenBalance,
+	/// The last block the invoice can be paid in.
+	pub expiry: BlockNumber,
+	/// A reference chosen by the merchant.
+	pub memo: Vec<u8>,
+	/// The account which paid the invoice, if it was paid.
+	pub paid_by: Option<AccountId>,
+	/// The amount reserved from the merchant while the invoice is stored.
+	pub deposit: Balance,
+}
+
+pub type BalanceOf<T> =
+	<<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
+
+pub type InvoiceOf<T> = Invoice<
+	<T as frame_system::Trait>::AccountId,
+	<T as zenlink_assets::Trait>::AssetId,
+	<T as zenlink_assets::Trait>::TokenBalance,
+	<T as frame_system::Trait>::BlockNumber,
+	BalanceOf<T>,
+>;
+
+pub trait Trait: frame_system::Trait + zenlink_assets::Trait + zenlink_dex::Trait {
+	/// The overarching event type.
+	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+
+	/// The maximum length of an invoice memo.
+	type MaxMemoLength: Get<u32>;
+
+	/// The currency the invoice deposit is reserved in.
+	type Currency: ReservableCurrency<Self::AccountId>;
+
+	/// The amount reserved from a merchant per stored invoice.
+	type InvoiceDeposit: Get<BalanceOf<Self>>;
+}
+
+decl_storage! {
+	trait Store for Module<T: Trait> as Invoice {
+		/// The invoices by id.
+		pub Invoices get(fn invoices): map hasher(twox_64_concat) InvoiceId => Option<InvoiceOf<T>>;
+
+		/// The id of the next invoice.
+		pub NextInvoiceId get(fn next_invoice_id): InvoiceId;
+	}
+}
+
+decl_event! {
+	pub enum Event<T> where
+		AccountId = <T as frame_system::Trait>::AccountId,
+		AssetId = <T as zenlink_assets::Trait>::AssetId,
+		TokenBalance = <T as zenlink_assets::Trait>::TokenBalance,
+		BlockNumber = <T as frame_system::Trait>::BlockNumber,
+	{
+		/// An invoice was created. \[id, merchant, asset_id, amount, expiry, memo\]
+		InvoiceCreated(InvoiceId, AccountId, AssetId, TokenBalance, BlockNumber, Vec<u8>),
+		/// An invoice was paid. \[id, merchant, payer, memo\]
+		InvoicePaid(InvoiceId, AccountId, AccountId, Vec<u8>),
+		/// An invoice was paid with another asset, swapped through the dex.
+		/// \[id, merchant, payer, paid_with, memo\]
+		InvoicePaidWithSwap(InvoiceId, AccountId, AccountId, AssetId, Vec<u8>),
+		/// An invoice was removed and its deposit returned. \[id\]
+		InvoiceRemoved(InvoiceId),
+	}
+}
+
+decl_error! {
+	pub enum Error for Module<T: Trait> {
+		/// There is no invoice with this id.
+		InvoiceNotFound,
+		/// The invoice was already paid.
+		AlreadyPaid,
+		/// The invoice is past its expiry.
+		InvoiceExpired,
+		/// Only the merchant can remove an invoice.
+		NotMerchant,
+		/// The amount must be positive.
+		ZeroAmount,
+		/// The memo is longer than `MaxMemoLength`.
+		MemoTooLong,
+		/// The dex has no exchange for the asset paid with.
+		NoExchange,
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
+		/// The maximum length of an invoice memo.
+		const MaxMemoLength: u32 = T::MaxMemoLength::get();
+
+		/// The amount reserved from a merchant per stored invoice.
+		const InvoiceDeposit: BalanceOf<T> = T::InvoiceDeposit::get();
+
+		fn deposit_event() = default;
+
+		/// Request `amount` of `asset_id` to be paid to the origin within `duration` blocks.
+		///
+		/// `InvoiceDeposit` is reserved from the origin until the invoice is removed.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(2, 3)]
+		pub fn create_invoice(
+			origin,
+			asset_id: T::AssetId,
+			amount: T::TokenBalance,
+			duration: T::BlockNumber,
+			memo: Vec<u8>,
+		) {
+			let merchant = ensure_signed(origin)?;
+
+			ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+			ensure!(memo.len() <= T::MaxMemoLength::get() as usize, Error::<T>::MemoTooLong);
+
+			let deposit = T::InvoiceDeposit::get();
+			T::Currency::reserve(&merchant, deposit)?;
+
+			let expiry = frame_system::Module::<T>::block_number().saturating_add(duration);
+			let id = Self::next_invoice_id();
+			NextInvoiceId::put(id.saturating_add(1));
+
+			Invoices::<T>::insert(id, Invoice {
+				merchant: merchant.clone(),
+				asset_id,
+				amount,
+				expiry,
+				memo: memo.clone(),
+				paid_by: None,
+				deposit,
+			});
+
+			Self::deposit_event(RawEvent::InvoiceCreated(id, merchant, asset_id, amount, expiry, memo));
+		}
+
+		/// Pay invoice `id` from the origin's balance.
+		///
+		/// With `pay_with`, the origin pays in that asset instead, spending at most the given
+		/// amount of it on a dex swap for exactly the invoice amount.
+		#[weight = 200_000_000 + T::DbWeight::get().reads_writes(10, 8)]
+		#[transactional]
+		pub fn pay_invoice(origin, id: InvoiceId, pay_with: Option<(T::AssetId, T::TokenBalance)>) {
+			let payer = ensure_signed(origin)?;
+
+			let mut invoice = Self::invoices(id).ok_or(Error::<T>::InvoiceNotFound)?;
+			ensure!(invoice.paid_by.is_none(), Error::<T>::AlreadyPaid);
+			let now = frame_system::Module::<T>::block_number();
+			ensure!(now <= invoice.expiry, Error::<T>::InvoiceExpired);
+
+			match pay_with {
+				Some((asset_id, max_amount)) if asset_id != invoice.asset_id => {
+					Self::swap_to_merchant(&payer, &invoice, asset_id, max_amount, now)?;
+
+					Self::deposit_event(RawEvent::InvoicePaidWithSwap(
+						id,
+						invoice.merchant.clone(),
+						payer.clone(),
+						asset_id,
+						invoice.memo.clone(),
+					));
+				}
+				_ => {
+					zenlink_assets::Module::<T>::inner_transfer(
+						&invoice.asset_id,
+						&payer,
+						&invoice.merchant,
+						invoice.amount,
+					)?;
+
+					Self::deposit_event(RawEvent::InvoicePaid(
+						id,
+						invoice.merchant.clone(),
+						payer.clone(),
+						invoice.memo.clone(),
+					));
+				}
+			}
+
+			invoice.paid_by = Some(payer);
+			Invoices::<T>::insert(id, invoice);
+		}
+
+		/// Remove invoice `id` of the origin, paid or not, and return its deposit.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(2, 2)]
+		pub fn remove_invoice(origin, id: InvoiceId) {
+			let who = ensure_signed(origin)?;
+
+			let invoice = Self::invoices(id).ok_or(Error::<T>::InvoiceNotFound)?;
+			ensure!(invoice.merchant == who, Error::<T>::NotMerchant);
+
+			Invoices::<T>::remove(id);
+			T::Currency::unreserve(&who, invoice.deposit);
+
+			Self::deposit_event(RawEvent::InvoiceRemoved(id));
+		}
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// Swap at most `max_amount` of `asset_id` from `payer` for exactly the amount of `invoice`,
+	/// paid to its merchant.
+	fn swap_to_merchant(
+		payer: &T::AccountId,
+		invoice: &InvoiceOf<T>,
+		asset_id: T::AssetId,
+		max_amount: T::TokenBalance,
+		now: T::BlockNumber,
+	) -> DispatchResult {
+		let exchange = zenlink_dex::Module::<T>::get_exchange_by_token_id(asset_id).ok_or(Error::<T>::NoExchange)?;
+
+		// The exchange takes the sold asset through the payer's allowance. It is set for the
+		// swap and put back afterwards, so other approvals of the payer are left alone.
+		let allowance = zenlink_dex::Module::<T>::get_token_allowance(asset_id, payer.clone(), exchange.account.clone());
+		zenlink_assets::Module::<T>::inner_approve(&asset_id, payer, &exchange.account, max_amount)?;
+
+		zenlink_dex::Module::<T>::token_to_token_output(
+			RawOrigin::Signed(payer.clone()).into(),
+			SwapHandler::AssetId(asset_id),
+			SwapHandler::AssetId(invoice.asset_id),
+			invoice.amount,
+			max_amount,
+			now,
+			invoice.merchant.clone(),
+		)?;
+
+		zenlink_assets::Module::<T>::inner_approve(&asset_id, payer, &exchange.account, allowance)
+	}
+}
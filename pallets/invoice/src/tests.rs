@@ -0,0 +1,123 @@
+//! Tests for the invoice module.
+
+use crate::{mock::*, Error, RawEvent};
+use frame_support::{assert_noop, assert_ok};
+
+const AMOUNT: TokenBalance = 10;
+
+fn has_event(event: RawEvent<AccountId, AssetId, TokenBalance, BlockNumber>) -> bool {
+	System::events().iter().any(|record| record.event == TestEvent::invoice(event.clone()))
+}
+
+/// Create an invoice of `AMOUNT` of `asset_id`, payable until block 11.
+fn create(asset_id: AssetId) {
+	assert_ok!(Invoice::create_invoice(Origin::signed(MERCHANT), asset_id, AMOUNT, 10, b"order 1".to_vec()));
+}
+
+/// Issue the invoice asset and an asset to pay with, both traded by an exchange, and give the
+/// payer 200 of each.
+fn setup() -> (AssetId, AssetId) {
+	let invoice_asset = issue(PROVIDER, 10_000);
+	let other_asset = issue(PROVIDER, 10_000);
+	for asset_id in &[invoice_asset, other_asset] {
+		assert_ok!(Dex::create_exchange(Origin::signed(PROVIDER), *asset_id, 500, 5_000));
+		assert_ok!(Assets::inner_transfer(asset_id, &PROVIDER, &PAYER, 200));
+	}
+	(invoice_asset, other_asset)
+}
+
+#[test]
+fn create_invoice_reserves_the_deposit() {
+	new_test_ext().execute_with(|| {
+		let (invoice_asset, _) = setup();
+		create(invoice_asset);
+
+		assert_eq!(Balances::reserved_balance(MERCHANT), DEPOSIT);
+		let invoice = Invoice::invoices(0).unwrap();
+		assert_eq!(invoice.expiry, 11);
+		assert_eq!(invoice.deposit, DEPOSIT);
+
+		assert_ok!(Invoice::remove_invoice(Origin::signed(MERCHANT), 0));
+		assert_eq!(Balances::reserved_balance(MERCHANT), 0);
+	});
+}
+
+#[test]
+fn pay_invoice_transfers_the_amount() {
+	new_test_ext().execute_with(|| {
+		let (invoice_asset, _) = setup();
+		create(invoice_asset);
+
+		assert_ok!(Invoice::pay_invoice(Origin::signed(PAYER), 0, None));
+
+		assert_eq!(balance(invoice_asset, MERCHANT), AMOUNT);
+		assert_eq!(balance(invoice_asset, PAYER), 200 - AMOUNT);
+		assert_eq!(Invoice::invoices(0).unwrap().paid_by, Some(PAYER));
+		assert!(has_event(RawEvent::InvoicePaid(0, MERCHANT, PAYER, b"order 1".to_vec())));
+		assert_noop!(Invoice::pay_invoice(Origin::signed(PAYER), 0, None), Error::<Test>::AlreadyPaid);
+	});
+}
+
+#[test]
+fn expired_invoices_can_not_be_paid() {
+	new_test_ext().execute_with(|| {
+		let (invoice_asset, other_asset) = setup();
+		create(invoice_asset);
+
+		System::set_block_number(12);
+		assert_noop!(Invoice::pay_invoice(Origin::signed(PAYER), 0, None), Error::<Test>::InvoiceExpired);
+		assert_noop!(
+			Invoice::pay_invoice(Origin::signed(PAYER), 0, Some((other_asset, 100))),
+			Error::<Test>::InvoiceExpired
+		);
+		assert_noop!(Invoice::pay_invoice(Origin::signed(PAYER), 1, None), Error::<Test>::InvoiceNotFound);
+	});
+}
+
+#[test]
+fn pay_invoice_with_another_asset_swaps_to_the_merchant() {
+	new_test_ext().execute_with(|| {
+		let (invoice_asset, other_asset) = setup();
+		create(invoice_asset);
+		let exchange = Dex::get_exchange_by_token_id(other_asset).unwrap();
+
+		assert_ok!(Invoice::pay_invoice(Origin::signed(PAYER), 0, Some((other_asset, 100))));
+
+		assert_eq!(balance(invoice_asset, MERCHANT), AMOUNT);
+		assert_eq!(balance(invoice_asset, PAYER), 200);
+		let spent = 200 - balance(other_asset, PAYER);
+		assert!(spent > 0 && spent <= 100);
+		assert_eq!(Dex::get_token_allowance(other_asset, PAYER, exchange.account), 0);
+		assert_eq!(Invoice::invoices(0).unwrap().paid_by, Some(PAYER));
+		assert!(has_event(RawEvent::InvoicePaidWithSwap(0, MERCHANT, PAYER, other_asset, b"order 1".to_vec())));
+	});
+}
+
+#[test]
+fn failed_swap_leaves_the_invoice_unpaid() {
+	new_test_ext().execute_with(|| {
+		let (invoice_asset, other_asset) = setup();
+		create(invoice_asset);
+
+		// One unit of the other asset buys much less than the invoice amount.
+		assert!(Invoice::pay_invoice(Origin::signed(PAYER), 0, Some((other_asset, 1))).is_err());
+
+		assert_eq!(balance(invoice_asset, MERCHANT), 0);
+		assert_eq!(balance(other_asset, PAYER), 200);
+		assert_eq!(Invoice::invoices(0).unwrap().paid_by, None);
+	});
+}
+
+#[test]
+fn paying_with_an_asset_without_exchange_fails() {
+	new_test_ext().execute_with(|| {
+		let (invoice_asset, _) = setup();
+		let unlisted = issue(PAYER, 1_000);
+		create(invoice_asset);
+
+		assert_noop!(
+			Invoice::pay_invoice(Origin::signed(PAYER), 0, Some((unlisted, 100))),
+			Error::<Test>::NoExchange
+		);
+	});
+}
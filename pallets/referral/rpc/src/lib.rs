@@ -0,0 +1,101 @@
+//! RPC interface for the referral module.
+
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use pallet_referral_runtime_api::ReferralApi as ReferralRuntimeApi;
+
+#[rpc]
+pub trait ReferralApi<BlockHash, AccountId> {
+	/// The referrer bound to `who`, if any.
+	#[rpc(name = "referral_referrerOf")]
+	fn referrer_of(&self, who: AccountId, at: Option<BlockHash>) -> Result<Option<AccountId>>;
+
+	/// The accounts referred by `referrer` from index `start` on, at most `count` and at most
+	/// 100.
+	#[rpc(name = "referral_refereesOf")]
+	fn referees_of(
+		&self,
+		referrer: AccountId,
+		start: u32,
+		count: u32,
+		at: Option<BlockHash>,
+	) -> Result<Vec<AccountId>>;
+
+	/// The number of accounts referred by `referrer`.
+	#[rpc(name = "referral_refereeCount")]
+	fn referee_count(&self, referrer: AccountId, at: Option<BlockHash>) -> Result<u32>;
+}
+
+/// A struct that implements the [`ReferralApi`].
+pub struct Referral<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> Referral<C, B> {
+	/// Create new `Referral` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block, AccountId> ReferralApi<<Block as BlockT>::Hash, AccountId> for Referral<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: ReferralRuntimeApi<Block, AccountId>,
+	AccountId: Codec,
+{
+	fn referrer_of(
+		&self,
+		who: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<AccountId>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.referrer_of(&at, who).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn referees_of(
+		&self,
+		referrer: AccountId,
+		start: u32,
+		count: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<AccountId>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.referees_of(&at, referrer, start, count).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn referee_count(
+		&self,
+		referrer: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<u32> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.referee_count(&at, referrer).map_err(runtime_error_into_rpc_err)
+	}
+}
+
+const RUNTIME_ERROR: i64 = 1;
+
+/// Converts a runtime trap into an RPC error.
+fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(RUNTIME_ERROR),
+		message: "Runtime trapped".into(),
+		data: Some(format!("{:?}", err).into()),
+	}
+}
@@ -0,0 +1,23 @@
+//! Runtime API definition for the referral module.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	#[api_version(2)]
+	pub trait ReferralApi<AccountId> where
+		AccountId: Codec,
+	{
+		/// The referrer bound to `who`, if any.
+		fn referrer_of(who: AccountId) -> Option<AccountId>;
+
+		/// The accounts referred by `referrer` from index `start` on, at most `count` and at
+		/// most 100.
+		fn referees_of(referrer: AccountId, start: u32, count: u32) -> Vec<AccountId>;
+
+		/// The number of accounts referred by `referrer`.
+		fn referee_count(referrer: AccountId) -> u32;
+	}
+}
@@ -0,0 +1,138 @@
+//! # Referral Module
+//!
+//! An account can bind a referrer exactly once with `set_referrer`. The binding can not be
+//! changed afterwards, so modules paying out referral fees or boosted rewards can rely on it.
+//!
+//! Referrals can not form a cycle. To keep that check bounded, an account can only be bound to a
+//! referrer with at most [`Trait::MaxChainLength`] referrers above it.
+//!
+//! Other modules query the relationship through the [`ReferralProvider`] trait, clients through
+//! the `ReferralApi` runtime API, which lists the referees of a referrer in pages of at most
+//! [`MAX_PAGE_LENGTH`].
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::{decl_error, decl_event, decl_module, decl_storage, ensure, traits::Get, weights::Weight};
+use frame_system::ensure_signed;
+use sp_std::prelude::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+/// The maximum number of referees returned by [`Module::referees`].
+pub const MAX_PAGE_LENGTH: u32 = 100;
+
+/// Read access to the referral relationships for other modules.
+pub trait ReferralProvider<AccountId> {
+	/// The referrer bound to `who`, if any.
+	fn referrer_of(who: &AccountId) -> Option<AccountId>;
+}
+
+impl<AccountId> ReferralProvider<AccountId> for () {
+	fn referrer_of(_who: &AccountId) -> Option<AccountId> {
+		None
+	}
+}
+
+pub trait Trait: frame_system::Trait {
+	/// The overarching event type.
+	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+
+	/// The maximum number of referrers above a new referrer.
+	type MaxChainLength: Get<u32>;
+}
+
+decl_storage! {
+	trait Store for Module<T: Trait> as Referral {
+		/// The referrer of an account.
+		pub Referrers get(fn referrer): map hasher(blake2_128_concat) T::AccountId => Option<T::AccountId>;
+
+		/// The accounts referred by a referrer, in the order they were bound.
+		pub Referees: double_map hasher(blake2_128_concat) T::AccountId, hasher(twox_64_concat) u32 => Option<T::AccountId>;
+
+		/// The number of accounts referred by a referrer, which is the index of the next one.
+		pub RefereeCount get(fn referee_count): map hasher(blake2_128_concat) T::AccountId => u32;
+	}
+}
+
+decl_event! {
+	pub enum Event<T> where AccountId = <T as frame_system::Trait>::AccountId {
+		/// A referrer was bound to an account. \[who, referrer\]
+		ReferrerSet(AccountId, AccountId),
+	}
+}
+
+decl_error! {
+	pub enum Error for Module<T: Trait> {
+		/// The account already has a referrer.
+		AlreadyBound,
+		/// An account can not refer itself.
+		SelfReferral,
+		/// The referrer was referred by the account, directly or through other referrers.
+		CircularReferral,
+		/// The referrer has more than `MaxChainLength` referrers above it.
+		ChainTooLong,
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
+		/// The maximum number of referrers above a new referrer.
+		const MaxChainLength: u32 = T::MaxChainLength::get();
+
+		fn deposit_event() = default;
+
+		/// Bind `referrer` as the referrer of the origin.
+		///
+		/// Can only be called once per account.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(3 + T::MaxChainLength::get() as Weight, 3)]
+		pub fn set_referrer(origin, referrer: T::AccountId) {
+			let who = ensure_signed(origin)?;
+
+			ensure!(who != referrer, Error::<T>::SelfReferral);
+			ensure!(!Referrers::<T>::contains_key(&who), Error::<T>::AlreadyBound);
+
+			// The origin has no referrer yet, so a cycle can only form if it is above the referrer.
+			let mut above = Self::referrer(&referrer);
+			for _ in 0..T::MaxChainLength::get() {
+				match above {
+					Some(account) => {
+						ensure!(account != who, Error::<T>::CircularReferral);
+						above = Self::referrer(&account);
+					}
+					None => break,
+				}
+			}
+			ensure!(above.is_none(), Error::<T>::ChainTooLong);
+
+			let index = Self::referee_count(&referrer);
+			Referrers::<T>::insert(&who, &referrer);
+			Referees::<T>::insert(&referrer, index, &who);
+			RefereeCount::<T>::insert(&referrer, index.saturating_add(1));
+
+			Self::deposit_event(RawEvent::ReferrerSet(who, referrer));
+		}
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// The accounts referred by `referrer` from index `start` on, at most `count` and at most
+	/// `MAX_PAGE_LENGTH`.
+	pub fn referees(referrer: &T::AccountId, start: u32, count: u32) -> Vec<T::AccountId> {
+		let end = start
+			.saturating_add(count.min(MAX_PAGE_LENGTH))
+			.min(Self::referee_count(referrer));
+		(start..end).filter_map(|index| Referees::<T>::get(referrer, index)).collect()
+	}
+}
+
+impl<T: Trait> ReferralProvider<T::AccountId> for Module<T> {
+	fn referrer_of(who: &T::AccountId) -> Option<T::AccountId> {
+		Self::referrer(who)
+	}
+}
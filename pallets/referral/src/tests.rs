@@ -0,0 +1,101 @@
+//! Tests for the referral module.
+
+use crate::{mock::*, Error, RawEvent, ReferralProvider, MAX_PAGE_LENGTH};
+use frame_support::{assert_noop, assert_ok};
+
+/// Bind each account of `chain` to the next one as its referrer.
+fn bind_chain(chain: &[AccountId]) {
+	for pair in chain.windows(2) {
+		assert_ok!(Referral::set_referrer(Origin::signed(pair[0]), pair[1]));
+	}
+}
+
+#[test]
+fn set_referrer_binds_the_referrer() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Referral::set_referrer(Origin::signed(1), 2));
+
+		assert_eq!(Referral::referrer(1), Some(2));
+		assert_eq!(<Referral as ReferralProvider<_>>::referrer_of(&1), Some(2));
+		assert_eq!(Referral::referees(&2, 0, 10), vec![1]);
+		assert_eq!(Referral::referee_count(2), 1);
+		let event = TestEvent::referral(RawEvent::ReferrerSet(1, 2));
+		assert!(System::events().iter().any(|record| record.event == event));
+	});
+}
+
+#[test]
+fn self_referral_is_refused() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Referral::set_referrer(Origin::signed(1), 1), Error::<Test>::SelfReferral);
+	});
+}
+
+#[test]
+fn referrer_is_bound_once() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Referral::set_referrer(Origin::signed(1), 2));
+
+		assert_noop!(Referral::set_referrer(Origin::signed(1), 3), Error::<Test>::AlreadyBound);
+		assert_noop!(Referral::set_referrer(Origin::signed(1), 2), Error::<Test>::AlreadyBound);
+	});
+}
+
+#[test]
+fn direct_cycle_is_refused() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Referral::set_referrer(Origin::signed(1), 2));
+
+		assert_noop!(Referral::set_referrer(Origin::signed(2), 1), Error::<Test>::CircularReferral);
+	});
+}
+
+#[test]
+fn indirect_cycle_is_refused() {
+	new_test_ext().execute_with(|| {
+		bind_chain(&[1, 2, 3, 4]);
+
+		assert_noop!(Referral::set_referrer(Origin::signed(4), 1), Error::<Test>::CircularReferral);
+		assert_noop!(Referral::set_referrer(Origin::signed(4), 2), Error::<Test>::CircularReferral);
+		assert_ok!(Referral::set_referrer(Origin::signed(4), 5));
+	});
+}
+
+#[test]
+fn referrer_with_a_long_chain_is_refused() {
+	new_test_ext().execute_with(|| {
+		// 1 has `MAX_CHAIN_LENGTH` referrers above it, 0 one more.
+		bind_chain(&[0, 1, 2, 3, 4]);
+
+		assert_ok!(Referral::set_referrer(Origin::signed(10), 1));
+		assert_noop!(Referral::set_referrer(Origin::signed(11), 0), Error::<Test>::ChainTooLong);
+	});
+}
+
+#[test]
+fn referees_are_listed_in_pages() {
+	new_test_ext().execute_with(|| {
+		for referee in 1..=5 {
+			assert_ok!(Referral::set_referrer(Origin::signed(referee), 100));
+		}
+
+		assert_eq!(Referral::referees(&100, 0, 2), vec![1, 2]);
+		assert_eq!(Referral::referees(&100, 2, 2), vec![3, 4]);
+		assert_eq!(Referral::referees(&100, 4, 2), vec![5]);
+		assert_eq!(Referral::referees(&100, 6, 2), Vec::<AccountId>::new());
+		assert_eq!(Referral::referees(&100, u32::max_value(), u32::max_value()), Vec::<AccountId>::new());
+	});
+}
+
+#[test]
+fn referee_pages_are_limited() {
+	new_test_ext().execute_with(|| {
+		let referees = MAX_PAGE_LENGTH as AccountId + 1;
+		for referee in 1..=referees {
+			assert_ok!(Referral::set_referrer(Origin::signed(referee), 0));
+		}
+
+		assert_eq!(Referral::referees(&0, 0, u32::max_value()).len(), MAX_PAGE_LENGTH as usize);
+		assert_eq!(Referral::referees(&0, MAX_PAGE_LENGTH, u32::max_value()), vec![referees]);
+	});
+}
@@ -0,0 +1,217 @@
+//! # Rescue Module
+//!
+//! Recovers assets sent to a module account by mistake, e.g. tokens transferred to the dex
+//! module account instead of an exchange.
+//!
+//! A rescue is announced by [`Trait::RescueOrigin`] with `rescue_funds` and executed
+//! `RescueDelay` blocks later, giving everyone time to review it and the rescue origin time to
+//! cancel it with `cancel_rescue`. Only the accounts of [`Trait::RescuableModules`] can be
+//! rescued from; module accounts holding assets on behalf of users, like the auction escrow,
+//! should not be listed. Assets pooled in the dex, as reported by [`Trait::PooledAssets`], can not
+//! be rescued at all, so a rescue can not drain exchange liquidity.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode};
+use frame_support::{
+	decl_error, decl_event, decl_module, decl_storage, ensure,
+	traits::{EnsureOrigin, Get},
+	weights::Weight,
+};
+use sp_runtime::{
+	DispatchError, ModuleId, RuntimeDebug,
+	traits::{AccountIdConversion, One, Saturating, Zero},
+};
+use sp_std::prelude::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+pub type RescueId = u32;
+
+/// An announced transfer out of a module account.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Rescue<AccountId, AssetId, TokenBalance, BlockNumber> {
+	/// The module account to transfer from.
+	pub source: AccountId,
+	/// The asset to transfer.
+	pub asset_id: AssetId,
+	/// The amount to transfer.
+	pub amount: TokenBalance,
+	/// The account to transfer to.
+	pub to: AccountId,
+	/// The block the transfer is executed in.
+	pub execute_at: BlockNumber,
+}
+
+pub type RescueOf<T> = Rescue<
+	<T as frame_system::Trait>::AccountId,
+	<T as zenlink_assets::Trait>::AssetId,
+	<T as zenlink_assets::Trait>::TokenBalance,
+	<T as frame_system::Trait>::BlockNumber,
+>;
+
+/// Tells which assets are held by modules on behalf of users.
+pub trait PooledAssets<AssetId> {
+	/// Whether `asset_id` is pooled, e.g. traded by a dex exchange.
+	fn is_pooled(asset_id: &AssetId) -> bool;
+}
+
+impl<AssetId> PooledAssets<AssetId> for () {
+	fn is_pooled(_: &AssetId) -> bool {
+		false
+	}
+}
+
+pub trait Trait: frame_system::Trait + zenlink_assets::Trait {
+	/// The overarching event type.
+	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+
+	/// The origin announcing and cancelling rescues.
+	type RescueOrigin: EnsureOrigin<Self::Origin>;
+
+	/// The number of blocks between the announcement and the execution of a rescue.
+	type RescueDelay: Get<Self::BlockNumber>;
+
+	/// The modules whose accounts can be rescued from.
+	type RescuableModules: Get<Vec<ModuleId>>;
+
+	/// The assets which can not be rescued.
+	type PooledAssets: PooledAssets<Self::AssetId>;
+}
+
+decl_storage! {
+	trait Store for Module<T: Trait> as Rescue {
+		/// The announced rescues.
+		pub Rescues get(fn rescues): map hasher(twox_64_concat) RescueId => Option<RescueOf<T>>;
+
+		/// The id of the next rescue.
+		pub NextRescueId get(fn next_rescue_id): RescueId;
+
+		/// Index of the rescues by the block they are executed in.
+		pub RescueSchedule: double_map hasher(twox_64_concat) T::BlockNumber, hasher(twox_64_concat) RescueId => ();
+	}
+}
+
+decl_event! {
+	pub enum Event<T> where
+		AccountId = <T as frame_system::Trait>::AccountId,
+		AssetId = <T as zenlink_assets::Trait>::AssetId,
+		TokenBalance = <T as zenlink_assets::Trait>::TokenBalance,
+		BlockNumber = <T as frame_system::Trait>::BlockNumber,
+	{
+		/// A rescue was announced. \[id, source, asset_id, amount, to, execute_at\]
+		RescueAnnounced(RescueId, AccountId, AssetId, TokenBalance, AccountId, BlockNumber),
+		/// A rescue was cancelled. \[id\]
+		RescueCancelled(RescueId),
+		/// A rescue was executed. \[id\]
+		RescueExecuted(RescueId),
+		/// A rescue failed, e.g. because the source no longer holds the amount. \[id, error\]
+		RescueFailed(RescueId, DispatchError),
+	}
+}
+
+decl_error! {
+	pub enum Error for Module<T: Trait> {
+		/// The module is not one of `RescuableModules`.
+		NotRescuable,
+		/// There is no announced rescue with this id.
+		RescueNotFound,
+		/// The amount must be positive.
+		ZeroAmount,
+		/// The asset is pooled and can not be rescued.
+		AssetPooled,
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
+		/// The number of blocks between the announcement and the execution of a rescue.
+		const RescueDelay: T::BlockNumber = T::RescueDelay::get();
+
+		fn deposit_event() = default;
+
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let mut executed: Weight = 0;
+			for (id, _) in RescueSchedule::<T>::drain_prefix(&now) {
+				if let Some(rescue) = Rescues::<T>::take(id) {
+					Self::execute(id, rescue);
+					executed += 1;
+				}
+			}
+
+			T::DbWeight::get().reads_writes(1 + 4 * executed, 1 + 4 * executed)
+		}
+
+		/// Announce the transfer of `amount` of `asset_id` from the account of `module` to `to`.
+		///
+		/// The transfer is executed after `RescueDelay` blocks unless it is cancelled or the asset
+		/// has been pooled by then.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(2, 3)]
+		pub fn rescue_funds(
+			origin,
+			module: ModuleId,
+			asset_id: T::AssetId,
+			amount: T::TokenBalance,
+			to: T::AccountId,
+		) {
+			T::RescueOrigin::ensure_origin(origin)?;
+			ensure!(T::RescuableModules::get().contains(&module), Error::<T>::NotRescuable);
+			ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+			ensure!(!T::PooledAssets::is_pooled(&asset_id), Error::<T>::AssetPooled);
+
+			let source: T::AccountId = module.into_account();
+			let execute_at = frame_system::Module::<T>::block_number()
+				.saturating_add(T::RescueDelay::get())
+				.saturating_add(One::one());
+			let id = Self::next_rescue_id();
+			NextRescueId::put(id.saturating_add(1));
+
+			Rescues::<T>::insert(id, Rescue {
+				source: source.clone(),
+				asset_id,
+				amount,
+				to: to.clone(),
+				execute_at,
+			});
+			RescueSchedule::<T>::insert(&execute_at, id, ());
+
+			Self::deposit_event(RawEvent::RescueAnnounced(id, source, asset_id, amount, to, execute_at));
+		}
+
+		/// Cancel the announced rescue `id`.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(1, 2)]
+		pub fn cancel_rescue(origin, id: RescueId) {
+			T::RescueOrigin::ensure_origin(origin)?;
+
+			let rescue = Rescues::<T>::take(id).ok_or(Error::<T>::RescueNotFound)?;
+			RescueSchedule::<T>::remove(&rescue.execute_at, id);
+
+			Self::deposit_event(RawEvent::RescueCancelled(id));
+		}
+	}
+}
+
+impl<T: Trait> Module<T> {
+	fn execute(id: RescueId, rescue: RescueOf<T>) {
+		if T::PooledAssets::is_pooled(&rescue.asset_id) {
+			Self::deposit_event(RawEvent::RescueFailed(id, Error::<T>::AssetPooled.into()));
+			return;
+		}
+
+		match zenlink_assets::Module::<T>::inner_transfer(
+			&rescue.asset_id,
+			&rescue.source,
+			&rescue.to,
+			rescue.amount,
+		) {
+			Ok(()) => Self::deposit_event(RawEvent::RescueExecuted(id)),
+			Err(error) => Self::deposit_event(RawEvent::RescueFailed(id, error)),
+		}
+	}
+}
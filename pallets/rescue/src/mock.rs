@@ -0,0 +1,149 @@
+//! Test utilities
+
+use crate::{Module, PooledAssets, Trait};
+use frame_support::{impl_outer_event, impl_outer_origin, parameter_types, weights::Weight};
+use frame_system::EnsureRoot;
+use sp_core::H256;
+use sp_runtime::{
+	ModuleId, Perbill,
+	testing::Header,
+	traits::{AccountIdConversion, BlakeTwo256, IdentityLookup},
+};
+use std::cell::RefCell;
+use zenlink_assets::AssetInfo;
+
+pub type AccountId = u64;
+pub type AssetId = u32;
+pub type TokenBalance = u64;
+pub type BlockNumber = u64;
+
+pub const SENDER: AccountId = 1;
+pub const OWNER: AccountId = 2;
+
+/// A module whose account receives assets by mistake.
+pub const DEX: ModuleId = ModuleId(*b"zlk_dex1");
+/// A module escrowing assets for users.
+pub const ESCROW: ModuleId = ModuleId(*b"cvs/escr");
+
+impl_outer_origin! {
+	pub enum Origin for Test {}
+}
+
+mod rescue {
+	pub use crate::Event;
+}
+
+impl_outer_event! {
+	pub enum TestEvent for Test {
+		frame_system<T>,
+		zenlink_assets<T>,
+		rescue<T>,
+	}
+}
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct Test;
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const MaximumBlockWeight: Weight = 1024;
+	pub const MaximumBlockLength: u32 = 2 * 1024;
+	pub const AvailableBlockRatio: Perbill = Perbill::one();
+}
+
+impl frame_system::Trait for Test {
+	type BaseCallFilter = ();
+	type Origin = Origin;
+	type Call = ();
+	type Index = u64;
+	type BlockNumber = BlockNumber;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = TestEvent;
+	type BlockHashCount = BlockHashCount;
+	type MaximumBlockWeight = MaximumBlockWeight;
+	type DbWeight = ();
+	type BlockExecutionWeight = ();
+	type ExtrinsicBaseWeight = ();
+	type MaximumExtrinsicWeight = MaximumBlockWeight;
+	type MaximumBlockLength = MaximumBlockLength;
+	type AvailableBlockRatio = AvailableBlockRatio;
+	type Version = ();
+	type PalletInfo = ();
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+}
+
+impl zenlink_assets::Trait for Test {
+	type Event = TestEvent;
+	type TokenBalance = TokenBalance;
+	type AssetId = AssetId;
+}
+
+thread_local! {
+	static POOLED: RefCell<Vec<AssetId>> = RefCell::new(Vec::new());
+}
+
+/// Treats the assets passed to `pool` as pooled.
+pub struct MockPooledAssets;
+
+impl PooledAssets<AssetId> for MockPooledAssets {
+	fn is_pooled(asset_id: &AssetId) -> bool {
+		POOLED.with(|pooled| pooled.borrow().contains(asset_id))
+	}
+}
+
+/// Mark `asset_id` as pooled, e.g. because an exchange was created for it.
+pub fn pool(asset_id: AssetId) {
+	POOLED.with(|pooled| pooled.borrow_mut().push(asset_id));
+}
+
+parameter_types! {
+	pub const RescueDelay: BlockNumber = 10;
+	pub RescuableModules: Vec<ModuleId> = vec![DEX];
+}
+
+impl Trait for Test {
+	type Event = TestEvent;
+	type RescueOrigin = EnsureRoot<AccountId>;
+	type RescueDelay = RescueDelay;
+	type RescuableModules = RescuableModules;
+	type PooledAssets = MockPooledAssets;
+}
+
+pub type System = frame_system::Module<Test>;
+pub type Assets = zenlink_assets::Module<Test>;
+pub type Rescue = Module<Test>;
+
+/// The account of `DEX`.
+pub fn dex_account() -> AccountId {
+	DEX.into_account()
+}
+
+/// The balance of `who` in `asset_id`.
+pub fn balance(asset_id: AssetId, who: AccountId) -> TokenBalance {
+	Assets::balance_of(&asset_id, &who)
+}
+
+/// Externalities with a new asset of which 100 were sent to the dex account by mistake. The id
+/// of the asset is returned.
+pub fn new_test_ext() -> (sp_io::TestExternalities, AssetId) {
+	POOLED.with(|pooled| pooled.borrow_mut().clear());
+
+	let storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	let mut ext = sp_io::TestExternalities::new(storage);
+	let asset_id = ext.execute_with(|| {
+		System::set_block_number(1);
+
+		let info = AssetInfo { name: *b"test asset\0\0\0\0\0\0", symbol: *b"TEST\0\0\0\0", decimals: 0 };
+		let asset_id = Assets::inner_issue(&SENDER, 1_000, &info);
+		Assets::inner_transfer(&asset_id, &SENDER, &dex_account(), 100).unwrap();
+		asset_id
+	});
+	(ext, asset_id)
+}
@@ -0,0 +1,88 @@
+//! Tests for the rescue module.
+
+use crate::{mock::*, Error, RawEvent};
+use frame_support::{assert_noop, assert_ok, traits::OnInitialize};
+use sp_runtime::DispatchError;
+
+fn run_to(now: BlockNumber) {
+	System::set_block_number(now);
+	Rescue::on_initialize(now);
+}
+
+fn has_event(event: RawEvent<AccountId, AssetId, TokenBalance, BlockNumber>) -> bool {
+	System::events().iter().any(|record| record.event == TestEvent::rescue(event.clone()))
+}
+
+#[test]
+fn rescue_is_executed_after_the_delay() {
+	let (mut ext, asset_id) = new_test_ext();
+	ext.execute_with(|| {
+		assert_ok!(Rescue::rescue_funds(Origin::root(), DEX, asset_id, 100, OWNER));
+		assert_eq!(Rescue::rescues(0).unwrap().execute_at, 12);
+
+		run_to(11);
+		assert_eq!(balance(asset_id, OWNER), 0);
+
+		run_to(12);
+		assert_eq!(balance(asset_id, OWNER), 100);
+		assert_eq!(balance(asset_id, dex_account()), 0);
+		assert_eq!(Rescue::rescues(0), None);
+		assert!(has_event(RawEvent::RescueExecuted(0)));
+	});
+}
+
+#[test]
+fn rescue_requires_the_rescue_origin_and_a_rescuable_module() {
+	let (mut ext, asset_id) = new_test_ext();
+	ext.execute_with(|| {
+		assert_noop!(
+			Rescue::rescue_funds(Origin::signed(OWNER), DEX, asset_id, 100, OWNER),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Rescue::rescue_funds(Origin::root(), ESCROW, asset_id, 100, OWNER),
+			Error::<Test>::NotRescuable
+		);
+	});
+}
+
+#[test]
+fn pooled_assets_can_not_be_rescued() {
+	let (mut ext, asset_id) = new_test_ext();
+	ext.execute_with(|| {
+		pool(asset_id);
+
+		assert_noop!(
+			Rescue::rescue_funds(Origin::root(), DEX, asset_id, 100, OWNER),
+			Error::<Test>::AssetPooled
+		);
+	});
+}
+
+#[test]
+fn rescue_fails_if_the_asset_is_pooled_during_the_delay() {
+	let (mut ext, asset_id) = new_test_ext();
+	ext.execute_with(|| {
+		assert_ok!(Rescue::rescue_funds(Origin::root(), DEX, asset_id, 100, OWNER));
+		pool(asset_id);
+
+		run_to(12);
+		assert_eq!(balance(asset_id, OWNER), 0);
+		assert_eq!(balance(asset_id, dex_account()), 100);
+		assert!(has_event(RawEvent::RescueFailed(0, Error::<Test>::AssetPooled.into())));
+	});
+}
+
+#[test]
+fn cancelled_rescue_is_not_executed() {
+	let (mut ext, asset_id) = new_test_ext();
+	ext.execute_with(|| {
+		assert_ok!(Rescue::rescue_funds(Origin::root(), DEX, asset_id, 100, OWNER));
+		assert_ok!(Rescue::cancel_rescue(Origin::root(), 0));
+		assert_noop!(Rescue::cancel_rescue(Origin::root(), 0), Error::<Test>::RescueNotFound);
+
+		run_to(12);
+		assert_eq!(balance(asset_id, OWNER), 0);
+		assert_eq!(balance(asset_id, dex_account()), 100);
+	});
+}
@@ -0,0 +1,79 @@
+//! RPC interface for the reserves module.
+
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use pallet_reserves_runtime_api::ReservesApi as ReservesRuntimeApi;
+
+#[rpc]
+pub trait ReservesApi<BlockHash, AssetId, Attestation> {
+	/// The latest attestation of the reserves of `asset_id`, if any.
+	#[rpc(name = "reserves_latestAttestation")]
+	fn latest_attestation(&self, asset_id: AssetId, at: Option<BlockHash>) -> Result<Option<Attestation>>;
+
+	/// Whether new supply of `asset_id` can be minted.
+	#[rpc(name = "reserves_canMint")]
+	fn can_mint(&self, asset_id: AssetId, at: Option<BlockHash>) -> Result<bool>;
+}
+
+/// A struct that implements the [`ReservesApi`].
+pub struct Reserves<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> Reserves<C, B> {
+	/// Create new `Reserves` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block, AssetId, Attestation> ReservesApi<<Block as BlockT>::Hash, AssetId, Attestation>
+	for Reserves<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: ReservesRuntimeApi<Block, AssetId, Attestation>,
+	AssetId: Codec,
+	Attestation: Codec,
+{
+	fn latest_attestation(
+		&self,
+		asset_id: AssetId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<Attestation>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.latest_attestation(&at, asset_id).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn can_mint(
+		&self,
+		asset_id: AssetId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<bool> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.can_mint(&at, asset_id).map_err(runtime_error_into_rpc_err)
+	}
+}
+
+const RUNTIME_ERROR: i64 = 1;
+
+/// Converts a runtime trap into an RPC error.
+fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(RUNTIME_ERROR),
+		message: "Runtime trapped".into(),
+		data: Some(format!("{:?}", err).into()),
+	}
+}
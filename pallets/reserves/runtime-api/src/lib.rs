@@ -0,0 +1,18 @@
+//! Runtime API definition for the reserves module.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+
+sp_api::decl_runtime_apis! {
+	pub trait ReservesApi<AssetId, Attestation> where
+		AssetId: Codec,
+		Attestation: Codec,
+	{
+		/// The latest attestation of the reserves of `asset_id`, if any.
+		fn latest_attestation(asset_id: AssetId) -> Option<Attestation>;
+
+		/// Whether new supply of `asset_id` can be minted.
+		fn can_mint(asset_id: AssetId) -> bool;
+	}
+}
@@ -0,0 +1,276 @@
+//! # Reserves Module
+//!
+//! Proof of reserves for wrapped assets. The custodian of a wrapped asset registers a key with
+//! [`Trait::AdminOrigin`] and signs an [`Attestation`] of the reserves backing the asset, e.g. the
+//! balance of the custody address on the source chain, whenever it changes. Anyone can submit a
+//! signed attestation with `attest`. The last `HistoryDepth` attestations of every asset are
+//! kept.
+//!
+//! Every attestation is signed as of a block. It can not be submitted before that block nor
+//! once it is older than the maximum age of the asset, and it can not be older than the latest
+//! attestation.
+//!
+//! An asset can have a maximum attestation age. Once its latest attestation is older than that,
+//! minting it is frozen until the custodian attests again. Minting modules check this through
+//! [`ReserveGuard`].
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use frame_support::{
+	decl_error, decl_event, decl_module, decl_storage,
+	dispatch::Parameter,
+	ensure,
+	traits::{EnsureOrigin, Get},
+};
+use frame_system::ensure_signed;
+use sp_runtime::{
+	RuntimeDebug,
+	traits::{IdentifyAccount, Saturating, Verify, Zero},
+};
+use sp_std::prelude::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+/// Context prefixed to every signed attestation so attestation signatures can not be reused
+/// elsewhere.
+pub const RESERVES_CONTEXT: &[u8] = b"canvas/reserves";
+
+/// The registered custodian of a wrapped asset.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Custodian<AccountId, BlockNumber> {
+	/// The key attestations have to be signed with.
+	pub key: AccountId,
+	/// Minting is frozen once the latest attestation is older than this many blocks.
+	pub max_age: Option<BlockNumber>,
+}
+
+/// The reserves backing a wrapped asset as signed by its custodian.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Attestation<TokenBalance, BlockNumber> {
+	/// The amount held in reserve.
+	pub reserves: TokenBalance,
+	/// A reference to the reserves on the source chain, e.g. a transaction hash.
+	pub reference: Vec<u8>,
+	/// The block the custodian attested the reserves as of.
+	pub attested_at: BlockNumber,
+}
+
+pub type CustodianOf<T> = Custodian<
+	<T as frame_system::Trait>::AccountId,
+	<T as frame_system::Trait>::BlockNumber,
+>;
+
+pub type AttestationOf<T> = Attestation<
+	<T as zenlink_assets::Trait>::TokenBalance,
+	<T as frame_system::Trait>::BlockNumber,
+>;
+
+/// Decides whether an asset can be minted.
+pub trait ReserveGuard<AssetId> {
+	/// Whether new supply of `asset_id` can be minted.
+	fn can_mint(asset_id: &AssetId) -> bool;
+}
+
+impl<AssetId> ReserveGuard<AssetId> for () {
+	fn can_mint(_: &AssetId) -> bool {
+		true
+	}
+}
+
+pub trait Trait: frame_system::Trait + zenlink_assets::Trait {
+	/// The overarching event type.
+	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+
+	/// The signature attestations are signed with.
+	type Signature: Parameter + Verify<Signer = Self::Signer>;
+
+	/// The public key behind `Signature`, identifying an account.
+	type Signer: IdentifyAccount<AccountId = Self::AccountId>;
+
+	/// The origin registering custodians.
+	type AdminOrigin: EnsureOrigin<Self::Origin>;
+
+	/// The number of attestations kept per asset.
+	type HistoryDepth: Get<u32>;
+
+	/// The maximum length of an attestation reference.
+	type MaxReferenceLength: Get<u32>;
+}
+
+decl_storage! {
+	trait Store for Module<T: Trait> as Reserves {
+		/// The custodian of a wrapped asset.
+		pub Custodians get(fn custodian): map hasher(blake2_128_concat) T::AssetId => Option<CustodianOf<T>>;
+
+		/// The attestations of an asset by index.
+		pub Attestations get(fn attestation): double_map hasher(blake2_128_concat) T::AssetId, hasher(twox_64_concat) u32 => Option<AttestationOf<T>>;
+
+		/// The number of attestations made for an asset, which is the index of the next one.
+		pub AttestationCount get(fn attestation_count): map hasher(blake2_128_concat) T::AssetId => u32;
+	}
+}
+
+decl_event! {
+	pub enum Event<T> where
+		AccountId = <T as frame_system::Trait>::AccountId,
+		AssetId = <T as zenlink_assets::Trait>::AssetId,
+		TokenBalance = <T as zenlink_assets::Trait>::TokenBalance,
+	{
+		/// The custodian key of an asset was set. \[asset_id, key\]
+		CustodianSet(AssetId, AccountId),
+		/// The custodian of an asset was removed. \[asset_id\]
+		CustodianRemoved(AssetId),
+		/// The reserves of an asset were attested. \[asset_id, index, reserves\]
+		ReservesAttested(AssetId, u32, TokenBalance),
+	}
+}
+
+decl_error! {
+	pub enum Error for Module<T: Trait> {
+		/// The asset has no custodian.
+		NoCustodian,
+		/// The signature does not match the attestation and the custodian key.
+		BadSignature,
+		/// The reference is longer than `MaxReferenceLength`.
+		ReferenceTooLong,
+		/// The attestation index overflowed.
+		IndexOverflow,
+		/// The attestation is signed as of a future block.
+		FutureAttestation,
+		/// The attestation is older than the maximum age or the latest attestation.
+		StaleAttestation,
+		/// The asset does not exist.
+		UnknownAsset,
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
+		/// The number of attestations kept per asset.
+		const HistoryDepth: u32 = T::HistoryDepth::get();
+
+		/// The maximum length of an attestation reference.
+		const MaxReferenceLength: u32 = T::MaxReferenceLength::get();
+
+		fn deposit_event() = default;
+
+		/// Register `key` as the custodian key of `asset_id`.
+		///
+		/// With a `max_age`, minting the asset is frozen while its latest attestation is older
+		/// than `max_age` blocks.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(1, 1)]
+		pub fn set_custodian(origin, asset_id: T::AssetId, key: T::AccountId, max_age: Option<T::BlockNumber>) {
+			T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(zenlink_assets::Module::<T>::asset_info(&asset_id).is_some(), Error::<T>::UnknownAsset);
+
+			Custodians::<T>::insert(asset_id, Custodian { key: key.clone(), max_age });
+
+			Self::deposit_event(RawEvent::CustodianSet(asset_id, key));
+		}
+
+		/// Remove the custodian of `asset_id`. The attestation history is kept.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(1, 1)]
+		pub fn remove_custodian(origin, asset_id: T::AssetId) {
+			T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(Custodians::<T>::contains_key(asset_id), Error::<T>::NoCustodian);
+
+			Custodians::<T>::remove(asset_id);
+
+			Self::deposit_event(RawEvent::CustodianRemoved(asset_id));
+		}
+
+		/// Submit an attestation of the reserves of `asset_id` as of block `as_of` signed by its
+		/// custodian key.
+		///
+		/// The signature covers the index of the attestation, so every signed attestation can
+		/// only be submitted once.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(4, 3)]
+		pub fn attest(
+			origin,
+			asset_id: T::AssetId,
+			reserves: T::TokenBalance,
+			reference: Vec<u8>,
+			as_of: T::BlockNumber,
+			signature: T::Signature,
+		) {
+			ensure_signed(origin)?;
+
+			ensure!(
+				reference.len() <= T::MaxReferenceLength::get() as usize,
+				Error::<T>::ReferenceTooLong
+			);
+			let custodian = Self::custodian(asset_id).ok_or(Error::<T>::NoCustodian)?;
+			let index = Self::attestation_count(asset_id);
+
+			let now = frame_system::Module::<T>::block_number();
+			ensure!(as_of <= now, Error::<T>::FutureAttestation);
+			if let Some(max_age) = custodian.max_age {
+				ensure!(now <= as_of.saturating_add(max_age), Error::<T>::StaleAttestation);
+			}
+			if let Some(latest) = Self::latest_attestation(&asset_id) {
+				ensure!(as_of >= latest.attested_at, Error::<T>::StaleAttestation);
+			}
+
+			let payload = Self::signing_payload(&asset_id, index, &reserves, &reference, &as_of);
+			ensure!(signature.verify(&payload[..], &custodian.key), Error::<T>::BadSignature);
+
+			let count = index.checked_add(1).ok_or(Error::<T>::IndexOverflow)?;
+			AttestationCount::<T>::insert(asset_id, count);
+			Attestations::<T>::insert(asset_id, index, Attestation {
+				reserves,
+				reference,
+				attested_at: as_of,
+			});
+			if let Some(expired) = index.checked_sub(T::HistoryDepth::get()) {
+				Attestations::<T>::remove(asset_id, expired);
+			}
+
+			Self::deposit_event(RawEvent::ReservesAttested(asset_id, index, reserves));
+		}
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// The bytes the custodian has to sign for attestation `index` of `asset_id` as of block
+	/// `as_of`.
+	pub fn signing_payload(
+		asset_id: &T::AssetId,
+		index: u32,
+		reserves: &T::TokenBalance,
+		reference: &[u8],
+		as_of: &T::BlockNumber,
+	) -> Vec<u8> {
+		let genesis_hash = frame_system::Module::<T>::block_hash(T::BlockNumber::zero());
+		(RESERVES_CONTEXT, genesis_hash, asset_id, index, reserves, reference, as_of).encode()
+	}
+
+	/// The latest attestation of `asset_id`.
+	pub fn latest_attestation(asset_id: &T::AssetId) -> Option<AttestationOf<T>> {
+		Self::attestation_count(asset_id)
+			.checked_sub(1)
+			.and_then(|index| Self::attestation(asset_id, index))
+	}
+}
+
+impl<T: Trait> ReserveGuard<T::AssetId> for Module<T> {
+	fn can_mint(asset_id: &T::AssetId) -> bool {
+		let max_age = match Self::custodian(asset_id).and_then(|custodian| custodian.max_age) {
+			Some(max_age) => max_age,
+			None => return true,
+		};
+
+		let now = frame_system::Module::<T>::block_number();
+		Self::latest_attestation(asset_id)
+			.map_or(false, |attestation| now <= attestation.attested_at.saturating_add(max_age))
+	}
+}
@@ -0,0 +1,114 @@
+//! Test utilities
+
+use crate::{Module, Trait};
+use frame_support::{impl_outer_event, impl_outer_origin, parameter_types, weights::Weight};
+use frame_system::EnsureRoot;
+use sp_core::H256;
+use sp_runtime::{
+	Perbill,
+	testing::{Header, TestSignature, UintAuthorityId},
+	traits::{BlakeTwo256, IdentityLookup},
+};
+use zenlink_assets::AssetInfo;
+
+pub type AccountId = u64;
+pub type AssetId = u32;
+pub type TokenBalance = u64;
+pub type BlockNumber = u64;
+
+pub const SUBMITTER: AccountId = 1;
+pub const CUSTODIAN: AccountId = 2;
+
+/// The number of attestations kept per asset.
+pub const HISTORY_DEPTH: u32 = 2;
+
+impl_outer_origin! {
+	pub enum Origin for Test {}
+}
+
+mod reserves {
+	pub use crate::Event;
+}
+
+impl_outer_event! {
+	pub enum TestEvent for Test {
+		frame_system<T>,
+		zenlink_assets<T>,
+		reserves<T>,
+	}
+}
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct Test;
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const MaximumBlockWeight: Weight = 1024;
+	pub const MaximumBlockLength: u32 = 2 * 1024;
+	pub const AvailableBlockRatio: Perbill = Perbill::one();
+}
+
+impl frame_system::Trait for Test {
+	type BaseCallFilter = ();
+	type Origin = Origin;
+	type Call = ();
+	type Index = u64;
+	type BlockNumber = BlockNumber;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = TestEvent;
+	type BlockHashCount = BlockHashCount;
+	type MaximumBlockWeight = MaximumBlockWeight;
+	type DbWeight = ();
+	type BlockExecutionWeight = ();
+	type ExtrinsicBaseWeight = ();
+	type MaximumExtrinsicWeight = MaximumBlockWeight;
+	type MaximumBlockLength = MaximumBlockLength;
+	type AvailableBlockRatio = AvailableBlockRatio;
+	type Version = ();
+	type PalletInfo = ();
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+}
+
+impl zenlink_assets::Trait for Test {
+	type Event = TestEvent;
+	type TokenBalance = TokenBalance;
+	type AssetId = AssetId;
+}
+
+parameter_types! {
+	pub const HistoryDepth: u32 = HISTORY_DEPTH;
+	pub const MaxReferenceLength: u32 = 8;
+}
+
+impl Trait for Test {
+	type Event = TestEvent;
+	type Signature = TestSignature;
+	type Signer = UintAuthorityId;
+	type AdminOrigin = EnsureRoot<AccountId>;
+	type HistoryDepth = HistoryDepth;
+	type MaxReferenceLength = MaxReferenceLength;
+}
+
+pub type System = frame_system::Module<Test>;
+pub type Assets = zenlink_assets::Module<Test>;
+pub type Reserves = Module<Test>;
+
+/// Issue a new asset with `supply` owned by `owner`.
+pub fn issue(owner: AccountId, supply: TokenBalance) -> AssetId {
+	let info = AssetInfo { name: *b"test asset\0\0\0\0\0\0", symbol: *b"TEST\0\0\0\0", decimals: 0 };
+	Assets::inner_issue(&owner, supply, &info)
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	let mut ext = sp_io::TestExternalities::new(storage);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,183 @@
+//! Tests for the reserves module.
+
+use crate::{mock::*, Error, RawEvent, ReserveGuard};
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::{DispatchError, DispatchResult, testing::TestSignature};
+
+/// The maximum attestation age of the asset.
+const MAX_AGE: BlockNumber = 10;
+
+/// Issue an asset and register the custodian with `MAX_AGE`.
+fn setup() -> AssetId {
+	let asset_id = issue(SUBMITTER, 1_000);
+	assert_ok!(Reserves::set_custodian(Origin::root(), asset_id, CUSTODIAN, Some(MAX_AGE)));
+	asset_id
+}
+
+/// The custodian's signature of the next attestation of `asset_id`.
+fn sign(asset_id: AssetId, reserves: TokenBalance, as_of: BlockNumber) -> TestSignature {
+	let index = Reserves::attestation_count(asset_id);
+	TestSignature(CUSTODIAN, Reserves::signing_payload(&asset_id, index, &reserves, b"tx", &as_of))
+}
+
+fn attest(asset_id: AssetId, reserves: TokenBalance, as_of: BlockNumber) -> DispatchResult {
+	let signature = sign(asset_id, reserves, as_of);
+	Reserves::attest(Origin::signed(SUBMITTER), asset_id, reserves, b"tx".to_vec(), as_of, signature)
+}
+
+fn has_event(event: RawEvent<AccountId, AssetId, TokenBalance>) -> bool {
+	System::events().iter().any(|record| record.event == TestEvent::reserves(event.clone()))
+}
+
+#[test]
+fn set_custodian_requires_the_admin_and_an_existing_asset() {
+	new_test_ext().execute_with(|| {
+		let asset_id = issue(SUBMITTER, 1_000);
+
+		assert_noop!(
+			Reserves::set_custodian(Origin::signed(SUBMITTER), asset_id, CUSTODIAN, None),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Reserves::set_custodian(Origin::root(), asset_id + 1, CUSTODIAN, None),
+			Error::<Test>::UnknownAsset
+		);
+	});
+}
+
+#[test]
+fn attest_records_the_reserves() {
+	new_test_ext().execute_with(|| {
+		let asset_id = setup();
+		System::set_block_number(5);
+
+		assert_ok!(attest(asset_id, 500, 4));
+
+		let attestation = Reserves::latest_attestation(&asset_id).unwrap();
+		assert_eq!(attestation.reserves, 500);
+		assert_eq!(attestation.attested_at, 4);
+		assert_eq!(Reserves::attestation_count(asset_id), 1);
+		assert!(has_event(RawEvent::ReservesAttested(asset_id, 0, 500)));
+	});
+}
+
+#[test]
+fn future_dated_attestation_is_refused() {
+	new_test_ext().execute_with(|| {
+		let asset_id = setup();
+		System::set_block_number(5);
+
+		assert_noop!(attest(asset_id, 500, 6), Error::<Test>::FutureAttestation);
+	});
+}
+
+#[test]
+fn attestation_older_than_the_maximum_age_is_refused() {
+	new_test_ext().execute_with(|| {
+		let asset_id = setup();
+		System::set_block_number(20);
+
+		assert_noop!(attest(asset_id, 500, 20 - MAX_AGE - 1), Error::<Test>::StaleAttestation);
+		assert_ok!(attest(asset_id, 500, 20 - MAX_AGE));
+	});
+}
+
+#[test]
+fn attestation_older_than_the_latest_is_refused() {
+	new_test_ext().execute_with(|| {
+		let asset_id = setup();
+		System::set_block_number(5);
+		assert_ok!(attest(asset_id, 500, 5));
+
+		assert_noop!(attest(asset_id, 400, 4), Error::<Test>::StaleAttestation);
+		assert_ok!(attest(asset_id, 400, 5));
+	});
+}
+
+#[test]
+fn replayed_attestation_is_refused() {
+	new_test_ext().execute_with(|| {
+		let asset_id = setup();
+		let signature = sign(asset_id, 500, 1);
+		assert_ok!(Reserves::attest(Origin::signed(SUBMITTER), asset_id, 500, b"tx".to_vec(), 1, signature.clone()));
+
+		// The signature covers index 0, the next attestation has index 1.
+		assert_noop!(
+			Reserves::attest(Origin::signed(SUBMITTER), asset_id, 500, b"tx".to_vec(), 1, signature),
+			Error::<Test>::BadSignature
+		);
+	});
+}
+
+#[test]
+fn attestation_signed_by_another_key_is_refused() {
+	new_test_ext().execute_with(|| {
+		let asset_id = setup();
+		let payload = Reserves::signing_payload(&asset_id, 0, &500, b"tx", &1);
+
+		assert_noop!(
+			Reserves::attest(Origin::signed(SUBMITTER), asset_id, 500, b"tx".to_vec(), 1, TestSignature(SUBMITTER, payload)),
+			Error::<Test>::BadSignature
+		);
+	});
+}
+
+#[test]
+fn attestation_reference_is_limited() {
+	new_test_ext().execute_with(|| {
+		let asset_id = setup();
+		let reference = b"too long!".to_vec();
+		let payload = Reserves::signing_payload(&asset_id, 0, &500, &reference, &1);
+
+		assert_noop!(
+			Reserves::attest(Origin::signed(SUBMITTER), asset_id, 500, reference, 1, TestSignature(CUSTODIAN, payload)),
+			Error::<Test>::ReferenceTooLong
+		);
+	});
+}
+
+#[test]
+fn history_keeps_the_latest_attestations() {
+	new_test_ext().execute_with(|| {
+		let asset_id = setup();
+		for reserves in 1..=HISTORY_DEPTH as TokenBalance + 1 {
+			assert_ok!(attest(asset_id, reserves, 1));
+		}
+
+		assert_eq!(Reserves::attestation(asset_id, 0), None);
+		assert!(Reserves::attestation(asset_id, 1).is_some());
+		assert!(Reserves::attestation(asset_id, 2).is_some());
+	});
+}
+
+#[test]
+fn minting_is_frozen_while_the_latest_attestation_is_too_old() {
+	new_test_ext().execute_with(|| {
+		let asset_id = setup();
+		// Without an attestation yet.
+		assert!(!Reserves::can_mint(&asset_id));
+
+		assert_ok!(attest(asset_id, 500, 1));
+		assert!(Reserves::can_mint(&asset_id));
+
+		System::set_block_number(1 + MAX_AGE);
+		assert!(Reserves::can_mint(&asset_id));
+		System::set_block_number(2 + MAX_AGE);
+		assert!(!Reserves::can_mint(&asset_id));
+
+		assert_ok!(attest(asset_id, 500, 2 + MAX_AGE));
+		assert!(Reserves::can_mint(&asset_id));
+	});
+}
+
+#[test]
+fn minting_is_not_frozen_without_a_maximum_age() {
+	new_test_ext().execute_with(|| {
+		let asset_id = issue(SUBMITTER, 1_000);
+		assert!(Reserves::can_mint(&asset_id));
+
+		assert_ok!(Reserves::set_custodian(Origin::root(), asset_id, CUSTODIAN, None));
+		System::set_block_number(1_000);
+		assert!(Reserves::can_mint(&asset_id));
+	});
+}
@@ -0,0 +1,355 @@
+//! # Settlement Module
+//!
+//! Settles orders matched off-chain, e.g. by a hybrid orderbook venue, without going through the
+//! dex. Maker and taker each sign an [`Order`]; anyone can submit the matched pair with `settle`,
+//! which swaps the assets between the two parties.
+//!
+//! Every order carries a nonce which can only be used once per account. Makers can cancel an
+//! order before it is settled by using up its nonce with `cancel_order`.
+//!
+//! Market makers can also sign a [`Batch`] of transfers and dex swaps, e.g. to rebalance
+//! inventory across assets and accounts. `settle_batch` applies all of them or none, so the
+//! maker is never left holding the result of some legs only. Swaps are dispatched to the dex with
+//! the maker as signed origin. Batches use the same nonces as orders.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode};
+use frame_support::{
+	decl_error, decl_event, decl_module, decl_storage,
+	dispatch::{DispatchResult, Parameter},
+	ensure,
+	traits::Get,
+	transactional,
+	weights::Weight,
+};
+use frame_system::{ensure_signed, RawOrigin};
+use sp_runtime::{
+	RuntimeDebug,
+	traits::{IdentifyAccount, Verify, Zero},
+};
+use sp_std::prelude::*;
+use zenlink_dex::SwapHandler;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+/// Context prefixed to every signed order so order signatures can not be reused elsewhere.
+pub const ORDER_CONTEXT: &[u8] = b"canvas/order";
+
+/// Context prefixed to every signed batch so batch signatures can not be reused elsewhere.
+pub const BATCH_CONTEXT: &[u8] = b"canvas/batch";
+
+/// An order to give an amount of one asset for an amount of another.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Order<AccountId, AssetId, TokenBalance, BlockNumber> {
+	/// The account signing the order.
+	pub owner: AccountId,
+	/// The asset the owner gives.
+	pub give_asset: AssetId,
+	/// The amount the owner gives.
+	pub give_amount: TokenBalance,
+	/// The asset the owner gets.
+	pub get_asset: AssetId,
+	/// The amount the owner gets.
+	pub get_amount: TokenBalance,
+	/// The last block the order can be settled in.
+	pub expiry: BlockNumber,
+	/// Unique per owner; a settled or cancelled nonce can not be used again.
+	pub nonce: u64,
+}
+
+/// A transfer from the owner of a batch.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Transfer<AccountId, AssetId, TokenBalance> {
+	/// The asset to transfer.
+	pub asset_id: AssetId,
+	/// The receiving account.
+	pub to: AccountId,
+	/// The amount to transfer.
+	pub amount: TokenBalance,
+}
+
+/// A dex swap of the owner of a batch, buying an exact amount.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Swap<AssetId, TokenBalance> {
+	/// The asset to sell.
+	pub sell_asset: AssetId,
+	/// The most the owner sells.
+	pub max_sell_amount: TokenBalance,
+	/// The asset to buy.
+	pub buy_asset: AssetId,
+	/// The amount the owner buys.
+	pub buy_amount: TokenBalance,
+}
+
+/// A step of a batch.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum Leg<AccountId, AssetId, TokenBalance> {
+	/// Transfer from the owner.
+	Transfer(Transfer<AccountId, AssetId, TokenBalance>),
+	/// Swap through the dex, bought by the owner.
+	Swap(Swap<AssetId, TokenBalance>),
+}
+
+/// Transfers and swaps signed together, applied all or none.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Batch<AccountId, AssetId, TokenBalance, BlockNumber> {
+	/// The account signing the batch, sending the transfers and making the swaps.
+	pub owner: AccountId,
+	/// The legs, applied in order.
+	pub legs: Vec<Leg<AccountId, AssetId, TokenBalance>>,
+	/// The last block the batch can be settled in.
+	pub expiry: BlockNumber,
+	/// Unique per owner and shared with orders.
+	pub nonce: u64,
+}
+
+pub type OrderOf<T> = Order<
+	<T as frame_system::Trait>::AccountId,
+	<T as zenlink_assets::Trait>::AssetId,
+	<T as zenlink_assets::Trait>::TokenBalance,
+	<T as frame_system::Trait>::BlockNumber,
+>;
+
+pub type BatchOf<T> = Batch<
+	<T as frame_system::Trait>::AccountId,
+	<T as zenlink_assets::Trait>::AssetId,
+	<T as zenlink_assets::Trait>::TokenBalance,
+	<T as frame_system::Trait>::BlockNumber,
+>;
+
+pub trait Trait: frame_system::Trait + zenlink_assets::Trait + zenlink_dex::Trait {
+	/// The overarching event type.
+	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+
+	/// The signature orders are signed with.
+	type Signature: Parameter + Verify<Signer = Self::Signer>;
+
+	/// The public key behind `Signature`, identifying an account.
+	type Signer: IdentifyAccount<AccountId = Self::AccountId>;
+
+	/// The maximum number of legs in a batch.
+	type MaxBatchLength: Get<u32>;
+}
+
+decl_storage! {
+	trait Store for Module<T: Trait> as Settlement {
+		/// The order nonces of an account which were settled or cancelled.
+		pub UsedNonces get(fn nonce_used): double_map hasher(blake2_128_concat) T::AccountId, hasher(twox_64_concat) u64 => bool;
+	}
+}
+
+decl_event! {
+	pub enum Event<T> where AccountId = <T as frame_system::Trait>::AccountId {
+		/// A matched order pair was settled. \[maker, maker_nonce, taker, taker_nonce\]
+		OrdersSettled(AccountId, u64, AccountId, u64),
+		/// An order was cancelled. \[owner, nonce\]
+		OrderCancelled(AccountId, u64),
+		/// A batch was settled. \[owner, nonce\]
+		BatchSettled(AccountId, u64),
+	}
+}
+
+decl_error! {
+	pub enum Error for Module<T: Trait> {
+		/// The order or batch is past its expiry.
+		OrderExpired,
+		/// The order's nonce was already settled or cancelled.
+		NonceUsed,
+		/// The signature does not match the order or batch and its owner.
+		BadSignature,
+		/// The orders do not give and get the same assets and amounts.
+		OrdersMismatch,
+		/// Maker and taker are the same account.
+		SelfTrade,
+		/// An order gives or gets nothing, or a leg is empty.
+		ZeroAmount,
+		/// The batch is empty or longer than `MaxBatchLength`.
+		InvalidBatchLength,
+		/// No exchange trades the sold asset of a swap.
+		NoExchange,
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
+		/// The maximum number of legs in a batch.
+		const MaxBatchLength: u32 = T::MaxBatchLength::get();
+
+		fn deposit_event() = default;
+
+		/// Settle a matched pair of signed orders.
+		///
+		/// The taker's order must get exactly what the maker's order gives and the other way
+		/// round. Both nonces are used up.
+		#[weight = 100_000_000 + T::DbWeight::get().reads_writes(8, 6)]
+		#[transactional]
+		pub fn settle(
+			origin,
+			maker: OrderOf<T>,
+			maker_signature: T::Signature,
+			taker: OrderOf<T>,
+			taker_signature: T::Signature,
+		) {
+			ensure_signed(origin)?;
+
+			ensure!(maker.owner != taker.owner, Error::<T>::SelfTrade);
+			ensure!(
+				maker.give_asset == taker.get_asset &&
+					maker.get_asset == taker.give_asset &&
+					maker.give_amount == taker.get_amount &&
+					maker.get_amount == taker.give_amount,
+				Error::<T>::OrdersMismatch
+			);
+			ensure!(
+				!maker.give_amount.is_zero() && !maker.get_amount.is_zero(),
+				Error::<T>::ZeroAmount
+			);
+			Self::use_order(&maker, &maker_signature)?;
+			Self::use_order(&taker, &taker_signature)?;
+
+			zenlink_assets::Module::<T>::inner_transfer(
+				&maker.give_asset,
+				&maker.owner,
+				&taker.owner,
+				maker.give_amount,
+			)?;
+			zenlink_assets::Module::<T>::inner_transfer(
+				&taker.give_asset,
+				&taker.owner,
+				&maker.owner,
+				taker.give_amount,
+			)?;
+
+			Self::deposit_event(RawEvent::OrdersSettled(maker.owner, maker.nonce, taker.owner, taker.nonce));
+		}
+
+		/// Apply the legs of a signed batch, all or none. The batch nonce is used up.
+		///
+		/// The weight charges every leg as a swap, the more expensive kind.
+		#[weight = 10_000
+			+ 200_000_000 * batch.legs.len() as Weight
+			+ T::DbWeight::get().reads_writes(
+				2 + 8 * batch.legs.len() as Weight,
+				1 + 6 * batch.legs.len() as Weight,
+			)
+		]
+		#[transactional]
+		pub fn settle_batch(origin, batch: BatchOf<T>, signature: T::Signature) {
+			ensure_signed(origin)?;
+
+			ensure!(
+				!batch.legs.is_empty() &&
+					batch.legs.len() <= T::MaxBatchLength::get() as usize,
+				Error::<T>::InvalidBatchLength
+			);
+			let payload = Self::batch_signing_payload(&batch);
+			Self::use_nonce(&batch.owner, batch.nonce, batch.expiry, &payload, &signature)?;
+
+			for leg in &batch.legs {
+				match leg {
+					Leg::Transfer(transfer) => {
+						ensure!(!transfer.amount.is_zero(), Error::<T>::ZeroAmount);
+						zenlink_assets::Module::<T>::inner_transfer(
+							&transfer.asset_id,
+							&batch.owner,
+							&transfer.to,
+							transfer.amount,
+						)?;
+					}
+					Leg::Swap(swap) => Self::swap(&batch.owner, swap)?,
+				}
+			}
+
+			Self::deposit_event(RawEvent::BatchSettled(batch.owner, batch.nonce));
+		}
+
+		/// Cancel the origin's order with `nonce` so it can not be settled anymore.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(1, 1)]
+		pub fn cancel_order(origin, nonce: u64) {
+			let who = ensure_signed(origin)?;
+
+			ensure!(!Self::nonce_used(&who, nonce), Error::<T>::NonceUsed);
+			UsedNonces::<T>::insert(&who, nonce, true);
+
+			Self::deposit_event(RawEvent::OrderCancelled(who, nonce));
+		}
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// The bytes an owner has to sign for `order`.
+	pub fn signing_payload(order: &OrderOf<T>) -> Vec<u8> {
+		let genesis_hash = frame_system::Module::<T>::block_hash(T::BlockNumber::zero());
+		(ORDER_CONTEXT, genesis_hash, order).encode()
+	}
+
+	/// The bytes an owner has to sign for `batch`.
+	pub fn batch_signing_payload(batch: &BatchOf<T>) -> Vec<u8> {
+		let genesis_hash = frame_system::Module::<T>::block_hash(T::BlockNumber::zero());
+		(BATCH_CONTEXT, genesis_hash, batch).encode()
+	}
+
+	/// Buy `swap.buy_amount` for `owner` through the exchange of `swap.sell_asset`.
+	fn swap(owner: &T::AccountId, swap: &Swap<T::AssetId, T::TokenBalance>) -> DispatchResult {
+		ensure!(
+			!swap.max_sell_amount.is_zero() && !swap.buy_amount.is_zero(),
+			Error::<T>::ZeroAmount
+		);
+		let exchange = zenlink_dex::Module::<T>::get_exchange_by_token_id(swap.sell_asset)
+			.ok_or(Error::<T>::NoExchange)?;
+
+		// The exchange takes the sold asset through the owner's allowance. It is set for the
+		// swap and put back afterwards, so other approvals of the owner are left alone.
+		let allowance = zenlink_dex::Module::<T>::get_token_allowance(
+			swap.sell_asset,
+			owner.clone(),
+			exchange.account.clone(),
+		);
+		zenlink_assets::Module::<T>::inner_approve(&swap.sell_asset, owner, &exchange.account, swap.max_sell_amount)?;
+
+		zenlink_dex::Module::<T>::token_to_token_output(
+			RawOrigin::Signed(owner.clone()).into(),
+			SwapHandler::AssetId(swap.sell_asset),
+			SwapHandler::AssetId(swap.buy_asset),
+			swap.buy_amount,
+			swap.max_sell_amount,
+			frame_system::Module::<T>::block_number(),
+			owner.clone(),
+		)?;
+
+		zenlink_assets::Module::<T>::inner_approve(&swap.sell_asset, owner, &exchange.account, allowance)
+	}
+
+	/// Check `order` can be settled and use up its nonce.
+	fn use_order(order: &OrderOf<T>, signature: &T::Signature) -> DispatchResult {
+		let payload = Self::signing_payload(order);
+		Self::use_nonce(&order.owner, order.nonce, order.expiry, &payload, signature)
+	}
+
+	/// Check `payload` signed by `owner` with `nonce` can be used until `expiry` and use up the
+	/// nonce.
+	fn use_nonce(
+		owner: &T::AccountId,
+		nonce: u64,
+		expiry: T::BlockNumber,
+		payload: &[u8],
+		signature: &T::Signature,
+	) -> DispatchResult {
+		ensure!(
+			frame_system::Module::<T>::block_number() <= expiry,
+			Error::<T>::OrderExpired
+		);
+		ensure!(!Self::nonce_used(owner, nonce), Error::<T>::NonceUsed);
+		ensure!(signature.verify(payload, owner), Error::<T>::BadSignature);
+
+		UsedNonces::<T>::insert(owner, nonce, true);
+		Ok(())
+	}
+}
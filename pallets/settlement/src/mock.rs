@@ -0,0 +1,151 @@
+//! Test utilities
+
+use crate::{Module, Trait};
+use frame_support::{impl_outer_event, impl_outer_origin, parameter_types, weights::Weight};
+use sp_core::H256;
+use sp_runtime::{
+	ModuleId, Perbill,
+	testing::{Header, TestSignature, UintAuthorityId},
+	traits::{BlakeTwo256, IdentityLookup},
+};
+use zenlink_assets::AssetInfo;
+
+pub type AccountId = u64;
+pub type AssetId = u32;
+pub type TokenBalance = u64;
+pub type Balance = u64;
+pub type BlockNumber = u64;
+
+pub const MAKER: AccountId = 1;
+pub const TAKER: AccountId = 2;
+pub const OTHER: AccountId = 3;
+pub const PROVIDER: AccountId = 4;
+
+impl_outer_origin! {
+	pub enum Origin for Test {}
+}
+
+mod settlement {
+	pub use crate::Event;
+}
+
+impl_outer_event! {
+	pub enum TestEvent for Test {
+		frame_system<T>,
+		pallet_balances<T>,
+		zenlink_assets<T>,
+		zenlink_dex<T>,
+		settlement<T>,
+	}
+}
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct Test;
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const MaximumBlockWeight: Weight = 1024;
+	pub const MaximumBlockLength: u32 = 2 * 1024;
+	pub const AvailableBlockRatio: Perbill = Perbill::one();
+}
+
+impl frame_system::Trait for Test {
+	type BaseCallFilter = ();
+	type Origin = Origin;
+	type Call = ();
+	type Index = u64;
+	type BlockNumber = BlockNumber;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = TestEvent;
+	type BlockHashCount = BlockHashCount;
+	type MaximumBlockWeight = MaximumBlockWeight;
+	type DbWeight = ();
+	type BlockExecutionWeight = ();
+	type ExtrinsicBaseWeight = ();
+	type MaximumExtrinsicWeight = MaximumBlockWeight;
+	type MaximumBlockLength = MaximumBlockLength;
+	type AvailableBlockRatio = AvailableBlockRatio;
+	type Version = ();
+	type PalletInfo = ();
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: Balance = 1;
+}
+
+impl pallet_balances::Trait for Test {
+	type Balance = Balance;
+	type DustRemoval = ();
+	type Event = TestEvent;
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = frame_system::Module<Test>;
+	type MaxLocks = ();
+	type WeightInfo = ();
+}
+
+impl zenlink_assets::Trait for Test {
+	type Event = TestEvent;
+	type TokenBalance = TokenBalance;
+	type AssetId = AssetId;
+}
+
+parameter_types! {
+	pub const DexModuleId: ModuleId = ModuleId(*b"zlk_dex1");
+}
+
+impl zenlink_dex::Trait for Test {
+	type Event = TestEvent;
+	type ExchangeId = u32;
+	type Currency = Balances;
+	type ModuleId = DexModuleId;
+}
+
+parameter_types! {
+	pub const MaxBatchLength: u32 = 3;
+}
+
+impl Trait for Test {
+	type Event = TestEvent;
+	type Signature = TestSignature;
+	type Signer = UintAuthorityId;
+	type MaxBatchLength = MaxBatchLength;
+}
+
+pub type System = frame_system::Module<Test>;
+pub type Balances = pallet_balances::Module<Test>;
+pub type Assets = zenlink_assets::Module<Test>;
+pub type Dex = zenlink_dex::Module<Test>;
+pub type Settlement = Module<Test>;
+
+/// Issue a new asset with `supply` owned by `owner`.
+pub fn issue(owner: AccountId, supply: TokenBalance) -> AssetId {
+	let info = AssetInfo { name: *b"test asset\0\0\0\0\0\0", symbol: *b"TEST\0\0\0\0", decimals: 0 };
+	Assets::inner_issue(&owner, supply, &info)
+}
+
+/// The balance of `who` in `asset_id`.
+pub fn balance(asset_id: AssetId, who: AccountId) -> TokenBalance {
+	Assets::balance_of(&asset_id, &who)
+}
+
+/// Externalities in which the liquidity provider holds 1_000 of the native currency.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(PROVIDER, 1_000)],
+	}
+	.assimilate_storage(&mut storage)
+	.unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(storage);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
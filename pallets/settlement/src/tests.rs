@@ -0,0 +1,304 @@
+//! Tests for the settlement module.
+
+use crate::{mock::*, Batch, BatchOf, Error, Leg, Order, OrderOf, RawEvent, Swap, Transfer};
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::testing::TestSignature;
+
+/// Issue an asset held by the maker and an asset held by the taker, 1_000 each.
+fn setup() -> (AssetId, AssetId) {
+	(issue(MAKER, 1_000), issue(TAKER, 1_000))
+}
+
+/// An order of `owner` giving 100 of `give_asset` for 200 of `get_asset`, or the other way round
+/// for the taker, valid until block 10.
+fn order(owner: AccountId, give_asset: AssetId, get_asset: AssetId, nonce: u64) -> OrderOf<Test> {
+	let (give_amount, get_amount) = if owner == MAKER { (100, 200) } else { (200, 100) };
+	Order { owner, give_asset, give_amount, get_asset, get_amount, expiry: 10, nonce }
+}
+
+fn sign(order: &OrderOf<Test>) -> TestSignature {
+	TestSignature(order.owner, Settlement::signing_payload(order))
+}
+
+/// A batch of the maker valid until block 10.
+fn batch(legs: Vec<Leg<AccountId, AssetId, TokenBalance>>, nonce: u64) -> BatchOf<Test> {
+	Batch { owner: MAKER, legs, expiry: 10, nonce }
+}
+
+fn transfer(asset_id: AssetId, to: AccountId, amount: TokenBalance) -> Leg<AccountId, AssetId, TokenBalance> {
+	Leg::Transfer(Transfer { asset_id, to, amount })
+}
+
+/// Buy `buy_amount` of `buy_asset` for at most `max_sell_amount` of `sell_asset`.
+fn swap(
+	sell_asset: AssetId,
+	max_sell_amount: TokenBalance,
+	buy_asset: AssetId,
+	buy_amount: TokenBalance,
+) -> Leg<AccountId, AssetId, TokenBalance> {
+	Leg::Swap(Swap { sell_asset, max_sell_amount, buy_asset, buy_amount })
+}
+
+/// Issue two assets traded by exchanges and give the maker 200 of each.
+fn setup_exchanges() -> (AssetId, AssetId) {
+	let first = issue(PROVIDER, 10_000);
+	let second = issue(PROVIDER, 10_000);
+	for asset_id in &[first, second] {
+		assert_ok!(Dex::create_exchange(Origin::signed(PROVIDER), *asset_id, 500, 5_000));
+		assert_ok!(Assets::inner_transfer(asset_id, &PROVIDER, &MAKER, 200));
+	}
+	(first, second)
+}
+
+fn sign_batch(batch: &BatchOf<Test>) -> TestSignature {
+	TestSignature(batch.owner, Settlement::batch_signing_payload(batch))
+}
+
+fn has_event(event: RawEvent<AccountId>) -> bool {
+	System::events().iter().any(|record| record.event == TestEvent::settlement(event.clone()))
+}
+
+#[test]
+fn settle_swaps_the_assets_and_uses_up_both_nonces() {
+	new_test_ext().execute_with(|| {
+		let (maker_asset, taker_asset) = setup();
+		let maker = order(MAKER, maker_asset, taker_asset, 0);
+		let taker = order(TAKER, taker_asset, maker_asset, 5);
+
+		assert_ok!(Settlement::settle(Origin::signed(OTHER), maker.clone(), sign(&maker), taker.clone(), sign(&taker)));
+
+		assert_eq!(balance(maker_asset, TAKER), 100);
+		assert_eq!(balance(taker_asset, MAKER), 200);
+		assert!(Settlement::nonce_used(MAKER, 0));
+		assert!(Settlement::nonce_used(TAKER, 5));
+		assert!(has_event(RawEvent::OrdersSettled(MAKER, 0, TAKER, 5)));
+	});
+}
+
+#[test]
+fn settle_checks_both_signatures() {
+	new_test_ext().execute_with(|| {
+		let (maker_asset, taker_asset) = setup();
+		let maker = order(MAKER, maker_asset, taker_asset, 0);
+		let taker = order(TAKER, taker_asset, maker_asset, 0);
+
+		// Signed by the maker instead of the taker.
+		let forged = TestSignature(MAKER, Settlement::signing_payload(&taker));
+		assert_noop!(
+			Settlement::settle(Origin::signed(OTHER), maker.clone(), sign(&maker), taker.clone(), forged),
+			Error::<Test>::BadSignature
+		);
+
+		// Signed for other amounts.
+		let mut changed = maker.clone();
+		changed.give_amount = 50;
+		assert_noop!(
+			Settlement::settle(Origin::signed(OTHER), maker, sign(&changed), taker.clone(), sign(&taker)),
+			Error::<Test>::BadSignature
+		);
+	});
+}
+
+#[test]
+fn settled_order_can_not_be_replayed() {
+	new_test_ext().execute_with(|| {
+		let (maker_asset, taker_asset) = setup();
+		let maker = order(MAKER, maker_asset, taker_asset, 0);
+		let taker = order(TAKER, taker_asset, maker_asset, 0);
+		assert_ok!(Settlement::settle(Origin::signed(OTHER), maker.clone(), sign(&maker), taker.clone(), sign(&taker)));
+
+		assert_noop!(
+			Settlement::settle(Origin::signed(OTHER), maker.clone(), sign(&maker), taker.clone(), sign(&taker)),
+			Error::<Test>::NonceUsed
+		);
+
+		// A fresh taker order can not reuse the maker's order either.
+		let taker = order(TAKER, taker_asset, maker_asset, 1);
+		assert_noop!(
+			Settlement::settle(Origin::signed(OTHER), maker.clone(), sign(&maker), taker.clone(), sign(&taker)),
+			Error::<Test>::NonceUsed
+		);
+	});
+}
+
+#[test]
+fn cancelled_order_can_not_be_settled() {
+	new_test_ext().execute_with(|| {
+		let (maker_asset, taker_asset) = setup();
+		let maker = order(MAKER, maker_asset, taker_asset, 0);
+		let taker = order(TAKER, taker_asset, maker_asset, 0);
+
+		assert_ok!(Settlement::cancel_order(Origin::signed(MAKER), 0));
+		assert_noop!(Settlement::cancel_order(Origin::signed(MAKER), 0), Error::<Test>::NonceUsed);
+
+		assert_noop!(
+			Settlement::settle(Origin::signed(OTHER), maker.clone(), sign(&maker), taker.clone(), sign(&taker)),
+			Error::<Test>::NonceUsed
+		);
+	});
+}
+
+#[test]
+fn expired_order_is_refused() {
+	new_test_ext().execute_with(|| {
+		let (maker_asset, taker_asset) = setup();
+		let maker = order(MAKER, maker_asset, taker_asset, 0);
+		let taker = order(TAKER, taker_asset, maker_asset, 0);
+
+		System::set_block_number(11);
+		assert_noop!(
+			Settlement::settle(Origin::signed(OTHER), maker.clone(), sign(&maker), taker.clone(), sign(&taker)),
+			Error::<Test>::OrderExpired
+		);
+	});
+}
+
+#[test]
+fn mismatched_orders_are_refused() {
+	new_test_ext().execute_with(|| {
+		let (maker_asset, taker_asset) = setup();
+		let maker = order(MAKER, maker_asset, taker_asset, 0);
+		let mut taker = order(TAKER, taker_asset, maker_asset, 0);
+		taker.get_amount = 101;
+
+		assert_noop!(
+			Settlement::settle(Origin::signed(OTHER), maker.clone(), sign(&maker), taker.clone(), sign(&taker)),
+			Error::<Test>::OrdersMismatch
+		);
+	});
+}
+
+#[test]
+fn failing_transfer_rolls_back_the_nonces() {
+	new_test_ext().execute_with(|| {
+		let (maker_asset, taker_asset) = setup();
+		let maker = order(MAKER, maker_asset, taker_asset, 0);
+		let taker = order(TAKER, taker_asset, maker_asset, 0);
+		// The taker can no longer give what it signed for.
+		assert_ok!(Assets::inner_transfer(&taker_asset, &TAKER, &OTHER, 900));
+
+		assert!(Settlement::settle(Origin::signed(OTHER), maker.clone(), sign(&maker), taker.clone(), sign(&taker)).is_err());
+
+		assert_eq!(balance(maker_asset, MAKER), 1_000);
+		assert!(!Settlement::nonce_used(MAKER, 0));
+		assert!(!Settlement::nonce_used(TAKER, 0));
+	});
+}
+
+#[test]
+fn settle_batch_applies_every_transfer() {
+	new_test_ext().execute_with(|| {
+		let (maker_asset, _) = setup();
+		let batch = batch(vec![transfer(maker_asset, TAKER, 100), transfer(maker_asset, OTHER, 50)], 0);
+
+		assert_ok!(Settlement::settle_batch(Origin::signed(OTHER), batch.clone(), sign_batch(&batch)));
+
+		assert_eq!(balance(maker_asset, MAKER), 850);
+		assert_eq!(balance(maker_asset, TAKER), 100);
+		assert_eq!(balance(maker_asset, OTHER), 50);
+		assert!(Settlement::nonce_used(MAKER, 0));
+		assert!(has_event(RawEvent::BatchSettled(MAKER, 0)));
+
+		assert_noop!(
+			Settlement::settle_batch(Origin::signed(OTHER), batch.clone(), sign_batch(&batch)),
+			Error::<Test>::NonceUsed
+		);
+	});
+}
+
+#[test]
+fn settle_batch_checks_the_signature() {
+	new_test_ext().execute_with(|| {
+		let (maker_asset, _) = setup();
+		let batch = batch(vec![transfer(maker_asset, TAKER, 100)], 0);
+		let forged = TestSignature(TAKER, Settlement::batch_signing_payload(&batch));
+
+		assert_noop!(
+			Settlement::settle_batch(Origin::signed(TAKER), batch, forged),
+			Error::<Test>::BadSignature
+		);
+	});
+}
+
+#[test]
+fn failing_leg_rolls_back_the_whole_batch() {
+	new_test_ext().execute_with(|| {
+		let (maker_asset, taker_asset) = setup();
+		// The maker holds none of the taker's asset, so the second transfer fails.
+		let batch = batch(vec![transfer(maker_asset, TAKER, 100), transfer(taker_asset, OTHER, 50)], 0);
+
+		assert!(Settlement::settle_batch(Origin::signed(OTHER), batch.clone(), sign_batch(&batch)).is_err());
+
+		assert_eq!(balance(maker_asset, MAKER), 1_000);
+		assert_eq!(balance(maker_asset, TAKER), 0);
+		assert!(!Settlement::nonce_used(MAKER, 0));
+	});
+}
+
+#[test]
+fn empty_and_oversized_batches_are_refused() {
+	new_test_ext().execute_with(|| {
+		let (maker_asset, _) = setup();
+		let empty = batch(vec![], 0);
+		assert_noop!(
+			Settlement::settle_batch(Origin::signed(OTHER), empty.clone(), sign_batch(&empty)),
+			Error::<Test>::InvalidBatchLength
+		);
+
+		let oversized = batch(vec![transfer(maker_asset, TAKER, 1); 4], 0);
+		assert_noop!(
+			Settlement::settle_batch(Origin::signed(OTHER), oversized.clone(), sign_batch(&oversized)),
+			Error::<Test>::InvalidBatchLength
+		);
+	});
+}
+
+#[test]
+fn swap_leg_buys_through_the_dex_for_the_owner() {
+	new_test_ext().execute_with(|| {
+		let (sold, bought) = setup_exchanges();
+		let exchange = Dex::get_exchange_by_token_id(sold).unwrap();
+		// The second leg only succeeds with what the first one bought.
+		let batch = batch(vec![swap(sold, 100, bought, 50), transfer(bought, OTHER, 250)], 0);
+
+		assert_ok!(Settlement::settle_batch(Origin::signed(OTHER), batch.clone(), sign_batch(&batch)));
+
+		assert_eq!(balance(bought, MAKER), 0);
+		assert_eq!(balance(bought, OTHER), 250);
+		// Both exchanges price the assets alike, so about 50 was sold.
+		assert!(balance(sold, MAKER) >= 100);
+		assert!(balance(sold, MAKER) < 150);
+		assert_eq!(Dex::get_token_allowance(sold, MAKER, exchange.account), 0);
+		assert!(has_event(RawEvent::BatchSettled(MAKER, 0)));
+	});
+}
+
+#[test]
+fn failing_swap_rolls_back_the_whole_batch() {
+	new_test_ext().execute_with(|| {
+		let (sold, bought) = setup_exchanges();
+		// Selling at most 1 does not buy 10.
+		let batch = batch(vec![transfer(sold, TAKER, 50), swap(sold, 1, bought, 10)], 0);
+
+		assert!(Settlement::settle_batch(Origin::signed(OTHER), batch.clone(), sign_batch(&batch)).is_err());
+
+		assert_eq!(balance(sold, MAKER), 200);
+		assert_eq!(balance(sold, TAKER), 0);
+		assert_eq!(balance(bought, MAKER), 200);
+		assert!(!Settlement::nonce_used(MAKER, 0));
+	});
+}
+
+#[test]
+fn swap_leg_needs_an_exchange() {
+	new_test_ext().execute_with(|| {
+		let (_, bought) = setup_exchanges();
+		let unlisted = issue(MAKER, 1_000);
+		let batch = batch(vec![swap(unlisted, 100, bought, 10)], 0);
+
+		assert_noop!(
+			Settlement::settle_batch(Origin::signed(OTHER), batch.clone(), sign_batch(&batch)),
+			Error::<Test>::NoExchange
+		);
+	});
+}
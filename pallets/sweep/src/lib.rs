@@ -0,0 +1,217 @@
+//! # Sweep Module
+//!
+//! A dead man's switch for custodians. An owner authorizes a guardian to move assets to a fixed
+//! cold address with `authorize_sweep`. The guardian starts a sweep of an asset with
+//! `initiate_sweep`; it can be executed with `execute_sweep` once the owner's waiting period has
+//! passed, unless the owner cancels it with `cancel_sweep` in the meantime.
+//!
+//! The guardian can only ever move assets to the cold address chosen by the owner, and the
+//! waiting period is at least [`Trait::MinimumDelay`], so a compromised guardian key gives the
+//! owner time to react. Sweeps can only be initiated for existing assets the owner holds, so a
+//! guardian can not fill up the owner's pending sweeps.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode};
+use frame_support::{
+	decl_error, decl_event, decl_module, decl_storage, ensure,
+	traits::Get,
+	transactional,
+};
+use frame_system::ensure_signed;
+use sp_runtime::{
+	RuntimeDebug,
+	traits::{Saturating, Zero},
+};
+use sp_std::prelude::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+/// A standing authorization to sweep an owner's assets.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct SweepAuthorization<AccountId, BlockNumber> {
+	/// The account allowed to initiate sweeps.
+	pub guardian: AccountId,
+	/// The cold address assets are swept to.
+	pub destination: AccountId,
+	/// The number of blocks between initiating and executing a sweep.
+	pub delay: BlockNumber,
+}
+
+/// A sweep waiting for its delay to pass.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct PendingSweep<TokenBalance, BlockNumber> {
+	/// The amount to sweep.
+	pub amount: TokenBalance,
+	/// The first block the sweep can be executed in.
+	pub executable_at: BlockNumber,
+}
+
+pub type SweepAuthorizationOf<T> = SweepAuthorization<
+	<T as frame_system::Trait>::AccountId,
+	<T as frame_system::Trait>::BlockNumber,
+>;
+
+pub type PendingSweepOf<T> = PendingSweep<
+	<T as zenlink_assets::Trait>::TokenBalance,
+	<T as frame_system::Trait>::BlockNumber,
+>;
+
+pub trait Trait: frame_system::Trait + zenlink_assets::Trait {
+	/// The overarching event type.
+	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+
+	/// The shortest waiting period an owner can choose.
+	type MinimumDelay: Get<Self::BlockNumber>;
+}
+
+decl_storage! {
+	trait Store for Module<T: Trait> as Sweep {
+		/// The sweep authorization of an owner.
+		pub Authorizations get(fn authorization): map hasher(blake2_128_concat) T::AccountId => Option<SweepAuthorizationOf<T>>;
+
+		/// The pending sweeps per owner and asset.
+		pub PendingSweeps get(fn pending_sweep): double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) T::AssetId => Option<PendingSweepOf<T>>;
+	}
+}
+
+decl_event! {
+	pub enum Event<T> where
+		AccountId = <T as frame_system::Trait>::AccountId,
+		AssetId = <T as zenlink_assets::Trait>::AssetId,
+		TokenBalance = <T as zenlink_assets::Trait>::TokenBalance,
+		BlockNumber = <T as frame_system::Trait>::BlockNumber,
+	{
+		/// An owner authorized sweeps. \[owner, guardian, destination, delay\]
+		SweepAuthorized(AccountId, AccountId, AccountId, BlockNumber),
+		/// An owner revoked its authorization and pending sweeps. \[owner\]
+		AuthorizationRevoked(AccountId),
+		/// A guardian initiated a sweep. \[owner, asset_id, amount, executable_at\]
+		SweepInitiated(AccountId, AssetId, TokenBalance, BlockNumber),
+		/// An owner cancelled a pending sweep. \[owner, asset_id\]
+		SweepCancelled(AccountId, AssetId),
+		/// A sweep was executed. \[owner, asset_id, amount, destination\]
+		SweepExecuted(AccountId, AssetId, TokenBalance, AccountId),
+	}
+}
+
+decl_error! {
+	pub enum Error for Module<T: Trait> {
+		/// The owner has not authorized sweeps.
+		NotAuthorized,
+		/// Only the owner's guardian can initiate sweeps.
+		NotGuardian,
+		/// The delay is shorter than `MinimumDelay`.
+		DelayTooShort,
+		/// A sweep of this asset is already pending.
+		SweepPending,
+		/// There is no pending sweep of this asset.
+		NoPendingSweep,
+		/// The waiting period of the sweep has not passed yet.
+		SweepNotReady,
+		/// The amount must be positive.
+		ZeroAmount,
+		/// The asset does not exist.
+		UnknownAsset,
+		/// The owner holds none of the asset.
+		NothingToSweep,
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
+		/// The shortest waiting period an owner can choose.
+		const MinimumDelay: T::BlockNumber = T::MinimumDelay::get();
+
+		fn deposit_event() = default;
+
+		/// Allow `guardian` to sweep the origin's assets to `destination` after `delay` blocks.
+		///
+		/// Replaces an existing authorization; sweeps already pending keep their waiting period.
+		#[weight = 10_000 + T::DbWeight::get().writes(1)]
+		pub fn authorize_sweep(origin, guardian: T::AccountId, destination: T::AccountId, delay: T::BlockNumber) {
+			let owner = ensure_signed(origin)?;
+			ensure!(delay >= T::MinimumDelay::get(), Error::<T>::DelayTooShort);
+
+			Authorizations::<T>::insert(&owner, SweepAuthorization {
+				guardian: guardian.clone(),
+				destination: destination.clone(),
+				delay,
+			});
+
+			Self::deposit_event(RawEvent::SweepAuthorized(owner, guardian, destination, delay));
+		}
+
+		/// Revoke the origin's authorization and cancel all its pending sweeps.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(1, 2)]
+		pub fn revoke_authorization(origin) {
+			let owner = ensure_signed(origin)?;
+			ensure!(Authorizations::<T>::contains_key(&owner), Error::<T>::NotAuthorized);
+
+			Authorizations::<T>::remove(&owner);
+			PendingSweeps::<T>::remove_prefix(&owner);
+
+			Self::deposit_event(RawEvent::AuthorizationRevoked(owner));
+		}
+
+		/// Start sweeping `amount` of `asset_id` of `owner`. The origin must be the owner's
+		/// guardian, and the owner must hold some of the asset.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(4, 1)]
+		pub fn initiate_sweep(origin, owner: T::AccountId, asset_id: T::AssetId, amount: T::TokenBalance) {
+			let who = ensure_signed(origin)?;
+
+			let authorization = Self::authorization(&owner).ok_or(Error::<T>::NotAuthorized)?;
+			ensure!(authorization.guardian == who, Error::<T>::NotGuardian);
+			ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+			ensure!(zenlink_assets::Module::<T>::asset_info(&asset_id).is_some(), Error::<T>::UnknownAsset);
+			ensure!(
+				!zenlink_assets::Module::<T>::balance_of(&asset_id, &owner).is_zero(),
+				Error::<T>::NothingToSweep
+			);
+			ensure!(!PendingSweeps::<T>::contains_key(&owner, asset_id), Error::<T>::SweepPending);
+
+			let executable_at = frame_system::Module::<T>::block_number().saturating_add(authorization.delay);
+			PendingSweeps::<T>::insert(&owner, asset_id, PendingSweep { amount, executable_at });
+
+			Self::deposit_event(RawEvent::SweepInitiated(owner, asset_id, amount, executable_at));
+		}
+
+		/// Cancel the pending sweep of `asset_id` of the origin.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(1, 1)]
+		pub fn cancel_sweep(origin, asset_id: T::AssetId) {
+			let owner = ensure_signed(origin)?;
+			ensure!(PendingSweeps::<T>::contains_key(&owner, asset_id), Error::<T>::NoPendingSweep);
+
+			PendingSweeps::<T>::remove(&owner, asset_id);
+
+			Self::deposit_event(RawEvent::SweepCancelled(owner, asset_id));
+		}
+
+		/// Execute the pending sweep of `asset_id` of `owner` once its waiting period has passed.
+		///
+		/// Anyone can execute a sweep; the assets can only go to the owner's destination.
+		#[weight = 100_000_000 + T::DbWeight::get().reads_writes(4, 3)]
+		#[transactional]
+		pub fn execute_sweep(origin, owner: T::AccountId, asset_id: T::AssetId) {
+			ensure_signed(origin)?;
+
+			let sweep = Self::pending_sweep(&owner, asset_id).ok_or(Error::<T>::NoPendingSweep)?;
+			ensure!(
+				frame_system::Module::<T>::block_number() >= sweep.executable_at,
+				Error::<T>::SweepNotReady
+			);
+			let authorization = Self::authorization(&owner).ok_or(Error::<T>::NotAuthorized)?;
+
+			zenlink_assets::Module::<T>::inner_transfer(&asset_id, &owner, &authorization.destination, sweep.amount)?;
+			PendingSweeps::<T>::remove(&owner, asset_id);
+
+			Self::deposit_event(RawEvent::SweepExecuted(owner, asset_id, sweep.amount, authorization.destination));
+		}
+	}
+}
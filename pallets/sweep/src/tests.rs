@@ -0,0 +1,149 @@
+//! Tests for the sweep module.
+
+use crate::{mock::*, Error, RawEvent};
+use frame_support::{assert_noop, assert_ok};
+
+/// Issue an asset held by the owner and authorize the guardian to sweep it to the cold address.
+fn setup() -> AssetId {
+	let asset_id = issue(OWNER, 1_000);
+	assert_ok!(Sweep::authorize_sweep(Origin::signed(OWNER), GUARDIAN, COLD, MINIMUM_DELAY));
+	asset_id
+}
+
+fn has_event(event: RawEvent<AccountId, AssetId, TokenBalance, BlockNumber>) -> bool {
+	System::events().iter().any(|record| record.event == TestEvent::sweep(event.clone()))
+}
+
+#[test]
+fn authorize_sweep_requires_the_minimum_delay() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Sweep::authorize_sweep(Origin::signed(OWNER), GUARDIAN, COLD, MINIMUM_DELAY - 1),
+			Error::<Test>::DelayTooShort
+		);
+	});
+}
+
+#[test]
+fn sweep_is_executed_after_the_delay() {
+	new_test_ext().execute_with(|| {
+		let asset_id = setup();
+		assert_ok!(Sweep::initiate_sweep(Origin::signed(GUARDIAN), OWNER, asset_id, 400));
+		assert!(has_event(RawEvent::SweepInitiated(OWNER, asset_id, 400, 6)));
+
+		System::set_block_number(5);
+		assert_noop!(
+			Sweep::execute_sweep(Origin::signed(COLD), OWNER, asset_id),
+			Error::<Test>::SweepNotReady
+		);
+
+		System::set_block_number(6);
+		assert_ok!(Sweep::execute_sweep(Origin::signed(COLD), OWNER, asset_id));
+
+		assert_eq!(balance(asset_id, OWNER), 600);
+		assert_eq!(balance(asset_id, COLD), 400);
+		assert_eq!(Sweep::pending_sweep(OWNER, asset_id), None);
+		assert!(has_event(RawEvent::SweepExecuted(OWNER, asset_id, 400, COLD)));
+	});
+}
+
+#[test]
+fn only_the_guardian_initiates_sweeps() {
+	new_test_ext().execute_with(|| {
+		let asset_id = setup();
+
+		assert_noop!(
+			Sweep::initiate_sweep(Origin::signed(COLD), OWNER, asset_id, 400),
+			Error::<Test>::NotGuardian
+		);
+		assert_noop!(
+			Sweep::initiate_sweep(Origin::signed(GUARDIAN), COLD, asset_id, 400),
+			Error::<Test>::NotAuthorized
+		);
+	});
+}
+
+#[test]
+fn initiate_sweep_requires_an_existing_asset() {
+	new_test_ext().execute_with(|| {
+		let asset_id = setup();
+
+		assert_noop!(
+			Sweep::initiate_sweep(Origin::signed(GUARDIAN), OWNER, asset_id + 1, 400),
+			Error::<Test>::UnknownAsset
+		);
+	});
+}
+
+#[test]
+fn initiate_sweep_requires_a_balance() {
+	new_test_ext().execute_with(|| {
+		setup();
+		let other_asset = issue(COLD, 1_000);
+
+		assert_noop!(
+			Sweep::initiate_sweep(Origin::signed(GUARDIAN), OWNER, other_asset, 400),
+			Error::<Test>::NothingToSweep
+		);
+	});
+}
+
+#[test]
+fn one_sweep_per_asset_is_pending() {
+	new_test_ext().execute_with(|| {
+		let asset_id = setup();
+		assert_ok!(Sweep::initiate_sweep(Origin::signed(GUARDIAN), OWNER, asset_id, 400));
+
+		assert_noop!(
+			Sweep::initiate_sweep(Origin::signed(GUARDIAN), OWNER, asset_id, 100),
+			Error::<Test>::SweepPending
+		);
+	});
+}
+
+#[test]
+fn cancelled_sweep_is_not_executed() {
+	new_test_ext().execute_with(|| {
+		let asset_id = setup();
+		assert_ok!(Sweep::initiate_sweep(Origin::signed(GUARDIAN), OWNER, asset_id, 400));
+
+		assert_ok!(Sweep::cancel_sweep(Origin::signed(OWNER), asset_id));
+
+		System::set_block_number(6);
+		assert_noop!(
+			Sweep::execute_sweep(Origin::signed(COLD), OWNER, asset_id),
+			Error::<Test>::NoPendingSweep
+		);
+		assert_eq!(balance(asset_id, OWNER), 1_000);
+	});
+}
+
+#[test]
+fn revoking_cancels_pending_sweeps() {
+	new_test_ext().execute_with(|| {
+		let asset_id = setup();
+		assert_ok!(Sweep::initiate_sweep(Origin::signed(GUARDIAN), OWNER, asset_id, 400));
+
+		assert_ok!(Sweep::revoke_authorization(Origin::signed(OWNER)));
+
+		assert_eq!(Sweep::authorization(OWNER), None);
+		assert_eq!(Sweep::pending_sweep(OWNER, asset_id), None);
+	});
+}
+
+#[test]
+fn failing_sweep_stays_pending() {
+	new_test_ext().execute_with(|| {
+		let asset_id = setup();
+		assert_ok!(Sweep::initiate_sweep(Origin::signed(GUARDIAN), OWNER, asset_id, 400));
+		// The owner moves most of the asset elsewhere in the meantime.
+		assert_ok!(Assets::inner_transfer(&asset_id, &OWNER, &GUARDIAN, 700));
+
+		System::set_block_number(6);
+		assert!(Sweep::execute_sweep(Origin::signed(COLD), OWNER, asset_id).is_err());
+
+		assert_eq!(balance(asset_id, OWNER), 300);
+		assert_eq!(balance(asset_id, COLD), 0);
+		assert!(Sweep::pending_sweep(OWNER, asset_id).is_some());
+	});
+}
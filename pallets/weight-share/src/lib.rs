@@ -0,0 +1,209 @@
+//! # Weight Share Module
+//!
+//! Reserves block space for everything else by capping the weight a group of calls can use per
+//! block. The runtime selects the group with [`Trait::LimitedCalls`], e.g. all dex calls, so a
+//! trading frenzy can not crowd out contract calls.
+//!
+//! The cap is a share of the weight available to normal extrinsics and can be changed by
+//! [`Trait::UpdateOrigin`]. It is enforced by the [`CheckWeightShare`] signed extension, which
+//! has to be part of the runtime's `SignedExtra`. Like `CheckWeight`, it accounts the declared
+//! weight of a call before dispatch and refunds the unused part afterwards.
+//!
+//! Block authors stop building a block after a few transactions fail for exhausting resources,
+//! so limited calls failing at the back of the block must not hold up the rest. The extension
+//! adds [`Trait::OtherCallsPriority`] to the priority of every call outside the group, so those
+//! are included before the limited calls. Limited calls heavier than the whole share are
+//! rejected by the transaction pool.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode};
+use frame_support::{
+	decl_event, decl_module, decl_storage,
+	traits::{EnsureOrigin, Filter, Get},
+	weights::{DispatchInfo, PostDispatchInfo, Weight},
+};
+use sp_runtime::{
+	DispatchResult, Perbill,
+	traits::{DispatchInfoOf, Dispatchable, PostDispatchInfoOf, SignedExtension},
+	transaction_validity::{
+		InvalidTransaction, TransactionPriority, TransactionValidity, TransactionValidityError, ValidTransaction,
+	},
+};
+use sp_std::marker::PhantomData;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+pub trait Trait: frame_system::Trait {
+	/// The overarching event type.
+	type Event: From<Event> + Into<<Self as frame_system::Trait>::Event>;
+
+	/// The calls whose combined weight per block is limited.
+	type LimitedCalls: Filter<<Self as frame_system::Trait>::Call>;
+
+	/// The share used until governance sets one.
+	type DefaultShare: Get<Perbill>;
+
+	/// The origin allowed to change the share.
+	type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+	/// The priority added to calls outside `LimitedCalls`, so block authors include them before
+	/// the limited calls.
+	type OtherCallsPriority: Get<TransactionPriority>;
+}
+
+decl_storage! {
+	trait Store for Module<T: Trait> as WeightShare {
+		/// The share of the normal dispatch weight the limited calls can use per block.
+		pub Share get(fn share): Perbill = T::DefaultShare::get();
+
+		/// The weight used by limited calls in the current block.
+		pub Consumed get(fn consumed): Weight;
+	}
+}
+
+decl_event! {
+	pub enum Event {
+		/// The share was changed. \[share\]
+		ShareUpdated(Perbill),
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		/// The share used until governance sets one.
+		const DefaultShare: Perbill = T::DefaultShare::get();
+
+		/// The priority added to calls outside `LimitedCalls`.
+		const OtherCallsPriority: TransactionPriority = T::OtherCallsPriority::get();
+
+		fn deposit_event() = default;
+
+		fn on_initialize() -> Weight {
+			Consumed::kill();
+			T::DbWeight::get().writes(1)
+		}
+
+		/// Set the share of the normal dispatch weight the limited calls can use per block.
+		#[weight = 10_000 + T::DbWeight::get().writes(1)]
+		pub fn set_share(origin, share: Perbill) {
+			T::UpdateOrigin::ensure_origin(origin)?;
+
+			Share::put(share);
+
+			Self::deposit_event(Event::ShareUpdated(share));
+		}
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// The weight the limited calls can use per block.
+	pub fn limit() -> Weight {
+		let normal = T::AvailableBlockRatio::get() * T::MaximumBlockWeight::get();
+		Self::share() * normal
+	}
+
+	/// Account `weight` to the limited calls if it fits into the remaining share.
+	fn note_weight(weight: Weight) -> Result<(), TransactionValidityError> {
+		let consumed = Self::consumed().saturating_add(weight);
+		if consumed > Self::limit() {
+			return Err(InvalidTransaction::ExhaustsResources.into());
+		}
+		Consumed::put(consumed);
+		Ok(())
+	}
+
+	/// Return `weight` the limited calls accounted but did not use.
+	fn refund_weight(weight: Weight) {
+		Consumed::mutate(|consumed| *consumed = consumed.saturating_sub(weight));
+	}
+}
+
+/// Rejects limited calls once their share of the current block is used up.
+#[derive(Encode, Decode, Clone, Eq, PartialEq)]
+pub struct CheckWeightShare<T: Trait + Send + Sync>(PhantomData<T>);
+
+impl<T: Trait + Send + Sync> CheckWeightShare<T> {
+	pub fn new() -> Self {
+		Self(PhantomData)
+	}
+}
+
+impl<T: Trait + Send + Sync> sp_std::fmt::Debug for CheckWeightShare<T> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		write!(f, "CheckWeightShare")
+	}
+
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		Ok(())
+	}
+}
+
+impl<T: Trait + Send + Sync> SignedExtension for CheckWeightShare<T> where
+	<T as frame_system::Trait>::Call: Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
+{
+	const IDENTIFIER: &'static str = "CheckWeightShare";
+	type AccountId = T::AccountId;
+	type Call = <T as frame_system::Trait>::Call;
+	type AdditionalSigned = ();
+	/// Whether the call is limited.
+	type Pre = bool;
+
+	fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+		Ok(())
+	}
+
+	fn validate(
+		&self,
+		_who: &Self::AccountId,
+		call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> TransactionValidity {
+		if !T::LimitedCalls::filter(call) {
+			return Ok(ValidTransaction { priority: T::OtherCallsPriority::get(), ..Default::default() });
+		}
+
+		// A limited call heavier than the whole share can never be included.
+		if info.weight > Module::<T>::limit() {
+			return Err(InvalidTransaction::ExhaustsResources.into());
+		}
+		Ok(ValidTransaction::default())
+	}
+
+	fn pre_dispatch(
+		self,
+		_who: &Self::AccountId,
+		call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> Result<bool, TransactionValidityError> {
+		let limited = T::LimitedCalls::filter(call);
+		if limited {
+			Module::<T>::note_weight(info.weight)?;
+		}
+		Ok(limited)
+	}
+
+	fn post_dispatch(
+		limited: bool,
+		info: &DispatchInfoOf<Self::Call>,
+		post_info: &PostDispatchInfoOf<Self::Call>,
+		_len: usize,
+		_result: &DispatchResult,
+	) -> Result<(), TransactionValidityError> {
+		if limited {
+			let unspent = post_info.calc_unspent(info);
+			if unspent > 0 {
+				Module::<T>::refund_weight(unspent);
+			}
+		}
+		Ok(())
+	}
+}
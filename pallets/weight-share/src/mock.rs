@@ -0,0 +1,115 @@
+//! Test utilities
+
+use crate::{Module, Trait};
+use frame_support::{
+	impl_outer_dispatch, impl_outer_event, impl_outer_origin, parameter_types,
+	traits::Filter,
+	weights::Weight,
+};
+use frame_system::EnsureRoot;
+use sp_core::H256;
+use sp_runtime::{
+	Perbill,
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+	transaction_validity::TransactionPriority,
+};
+
+pub type AccountId = u64;
+
+pub const ALICE: AccountId = 1;
+
+/// The priority added to calls outside the limited group.
+pub const OTHER_CALLS_PRIORITY: TransactionPriority = 1_000;
+
+impl_outer_origin! {
+	pub enum Origin for Test {}
+}
+
+impl_outer_dispatch! {
+	pub enum Call for Test where origin: Origin {
+		frame_system::System,
+	}
+}
+
+mod weight_share {
+	pub use crate::Event;
+}
+
+impl_outer_event! {
+	pub enum TestEvent for Test {
+		frame_system<T>,
+		weight_share,
+	}
+}
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct Test;
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const MaximumBlockWeight: Weight = 1024;
+	pub const MaximumBlockLength: u32 = 2 * 1024;
+	pub const AvailableBlockRatio: Perbill = Perbill::from_percent(75);
+}
+
+impl frame_system::Trait for Test {
+	type BaseCallFilter = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = TestEvent;
+	type BlockHashCount = BlockHashCount;
+	type MaximumBlockWeight = MaximumBlockWeight;
+	type DbWeight = ();
+	type BlockExecutionWeight = ();
+	type ExtrinsicBaseWeight = ();
+	type MaximumExtrinsicWeight = MaximumBlockWeight;
+	type MaximumBlockLength = MaximumBlockLength;
+	type AvailableBlockRatio = AvailableBlockRatio;
+	type Version = ();
+	type PalletInfo = ();
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+}
+
+/// Limits remarks, standing in for the dex calls of the runtime.
+pub struct Remarks;
+
+impl Filter<Call> for Remarks {
+	fn filter(call: &Call) -> bool {
+		matches!(call, Call::System(frame_system::Call::remark(_)))
+	}
+}
+
+parameter_types! {
+	/// Remarks can use half of the normal dispatch weight, 384 of 768.
+	pub const DefaultShare: Perbill = Perbill::from_percent(50);
+	pub const OtherCallsPriority: TransactionPriority = OTHER_CALLS_PRIORITY;
+}
+
+impl Trait for Test {
+	type Event = TestEvent;
+	type LimitedCalls = Remarks;
+	type DefaultShare = DefaultShare;
+	type UpdateOrigin = EnsureRoot<AccountId>;
+	type OtherCallsPriority = OtherCallsPriority;
+}
+
+pub type System = frame_system::Module<Test>;
+pub type WeightShare = Module<Test>;
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	let mut ext = sp_io::TestExternalities::new(storage);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,88 @@
+//! Tests for the weight share module.
+
+use crate::{mock::*, CheckWeightShare};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::OnInitialize,
+	weights::{DispatchInfo, PostDispatchInfo, Weight},
+};
+use sp_runtime::{
+	DispatchError, Perbill,
+	traits::SignedExtension,
+	transaction_validity::{InvalidTransaction, TransactionValidityError},
+};
+
+fn info(weight: Weight) -> DispatchInfo {
+	DispatchInfo { weight, ..Default::default() }
+}
+
+fn remark() -> Call {
+	Call::System(frame_system::Call::remark(vec![]))
+}
+
+fn other() -> Call {
+	Call::System(frame_system::Call::set_heap_pages(1))
+}
+
+fn exhausted() -> TransactionValidityError {
+	InvalidTransaction::ExhaustsResources.into()
+}
+
+#[test]
+fn other_calls_are_prioritised() {
+	new_test_ext().execute_with(|| {
+		let check = CheckWeightShare::<Test>::new();
+
+		assert_eq!(check.validate(&ALICE, &remark(), &info(10), 0).unwrap().priority, 0);
+		assert_eq!(check.validate(&ALICE, &other(), &info(10), 0).unwrap().priority, OTHER_CALLS_PRIORITY);
+	});
+}
+
+#[test]
+fn limited_calls_heavier_than_the_share_are_invalid() {
+	new_test_ext().execute_with(|| {
+		let check = CheckWeightShare::<Test>::new();
+
+		assert_eq!(WeightShare::limit(), 384);
+		assert!(check.validate(&ALICE, &remark(), &info(384), 0).is_ok());
+		assert_eq!(check.validate(&ALICE, &remark(), &info(385), 0), Err(exhausted()));
+		assert!(check.validate(&ALICE, &other(), &info(385), 0).is_ok());
+	});
+}
+
+#[test]
+fn limited_calls_are_rejected_once_the_share_is_used() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(CheckWeightShare::<Test>::new().pre_dispatch(&ALICE, &remark(), &info(200), 0), Ok(true));
+		assert_eq!(WeightShare::consumed(), 200);
+
+		assert_eq!(CheckWeightShare::<Test>::new().pre_dispatch(&ALICE, &remark(), &info(200), 0), Err(exhausted()));
+		assert_eq!(CheckWeightShare::<Test>::new().pre_dispatch(&ALICE, &other(), &info(200), 0), Ok(false));
+		assert_eq!(WeightShare::consumed(), 200);
+
+		WeightShare::on_initialize(2);
+		assert_eq!(WeightShare::consumed(), 0);
+		assert_eq!(CheckWeightShare::<Test>::new().pre_dispatch(&ALICE, &remark(), &info(200), 0), Ok(true));
+	});
+}
+
+#[test]
+fn unused_weight_is_refunded() {
+	new_test_ext().execute_with(|| {
+		let pre = CheckWeightShare::<Test>::new().pre_dispatch(&ALICE, &remark(), &info(200), 0).unwrap();
+		let post_info = PostDispatchInfo { actual_weight: Some(50), pays_fee: Default::default() };
+
+		assert_ok!(CheckWeightShare::<Test>::post_dispatch(pre, &info(200), &post_info, 0, &Ok(())));
+		assert_eq!(WeightShare::consumed(), 50);
+	});
+}
+
+#[test]
+fn set_share_requires_the_update_origin() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(WeightShare::set_share(Origin::signed(ALICE), Perbill::from_percent(25)), DispatchError::BadOrigin);
+
+		assert_ok!(WeightShare::set_share(Origin::root(), Perbill::from_percent(25)));
+		assert_eq!(WeightShare::limit(), 192);
+	});
+}
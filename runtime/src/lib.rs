@@ -4,13 +4,18 @@
 
 pub use frame_support::{
 	construct_runtime, parameter_types, StorageValue,
-	traits::{KeyOwnerProofSystem, Randomness},
+	traits::{Filter, KeyOwnerProofSystem, Randomness},
 	weights::{
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND}, IdentityFee,
 		Weight,
 	},
 };
 pub use pallet_balances::Call as BalancesCall;
+#[cfg(feature = "with-dex")]
+use canvas_batch_read_runtime_api::{Answer, BatchReadError, Query};
+use canvas_dry_run_runtime_api::{DispatchErrorInfo, DryRunResult};
+#[cfg(feature = "with-dex")]
+use frame_system::{EnsureRoot, EnsureSigned};
 use pallet_contracts_rpc_runtime_api::ContractExecResult;
 use pallet_grandpa::{AuthorityId as GrandpaId, AuthorityList as GrandpaAuthorityList};
 use pallet_grandpa::fg_primitives;
@@ -19,10 +24,12 @@ use sp_api::impl_runtime_apis;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::{
-	ApplyExtrinsicResult, create_runtime_str, generic, impl_opaque_keys, ModuleId, MultiSignature,
+	ApplyExtrinsicResult, create_runtime_str, generic, impl_opaque_keys, MultiSignature,
 	transaction_validity::{TransactionSource, TransactionValidity},
 };
 pub use sp_runtime::{Perbill, Permill};
+#[cfg(feature = "with-dex")]
+use sp_runtime::{ModuleId, transaction_validity::TransactionPriority};
 // A few exports that help ease life for downstream crates.
 #[cfg(any(feature = "std", test))]
 pub use sp_runtime::BuildStorage;
@@ -33,6 +40,7 @@ use sp_std::prelude::*;
 #[cfg(feature = "std")]
 use sp_version::NativeVersion;
 use sp_version::RuntimeVersion;
+#[cfg(feature = "with-dex")]
 use zenlink_dex::{ExchangeInfo, TokenInfo};
 
 // Make the WASM binary available.
@@ -102,10 +110,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: create_runtime_str!("canvas"),
 	impl_name: create_runtime_str!("canvas"),
 	authoring_version: 1,
-	spec_version: 7,
+	spec_version: 41,
 	impl_version: 6,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 };
 
 pub const MILLISECS_PER_BLOCK: u64 = 6000;
@@ -297,16 +305,19 @@ impl pallet_sudo::Trait for Runtime {
 	type Call = Call;
 }
 
+#[cfg(feature = "with-dex")]
 impl zenlink_assets::Trait for Runtime {
 	type Event = Event;
 	type TokenBalance = TokenBalance;
 	type AssetId = AssetId;
 }
 
+#[cfg(feature = "with-dex")]
 parameter_types! {
     pub const DEXModuleId: ModuleId = ModuleId(*b"zlk_dex1");
 }
 
+#[cfg(feature = "with-dex")]
 impl zenlink_dex::Trait for Runtime {
 	type Event = Event;
 	type ExchangeId = ExchangeId;
@@ -314,26 +325,252 @@ impl zenlink_dex::Trait for Runtime {
 	type ModuleId = DEXModuleId;
 }
 
+parameter_types! {
+	pub const ReferralMaxChainLength: u32 = 32;
+}
+
+impl pallet_referral::Trait for Runtime {
+	type Event = Event;
+	type MaxChainLength = ReferralMaxChainLength;
+}
+
+/// Pays the fees of forwarded requests in zenlink assets.
+#[cfg(feature = "with-dex")]
+pub struct AssetRelayerFee;
+
+#[cfg(feature = "with-dex")]
+impl pallet_forwarder::RelayerFee<AccountId> for AssetRelayerFee {
+	type Fee = (AssetId, TokenBalance);
+
+	fn pay(who: &AccountId, relayer: &AccountId, fee: &Self::Fee) -> sp_runtime::DispatchResult {
+		ZenlinkAssets::inner_transfer(&fee.0, who, relayer, fee.1)
+	}
+
+	fn within(fee: &Self::Fee, max: &Self::Fee) -> bool {
+		fee.0 == max.0 && fee.1 <= max.1
+	}
+}
+
+parameter_types! {
+	pub const RelayerBond: Balance = 100 * DOLLARS;
+	pub const MaxRelayerFees: u32 = 16;
+}
+
+impl pallet_forwarder::Trait for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type Signature = Signature;
+	type Signer = <Signature as Verify>::Signer;
+	#[cfg(feature = "with-dex")]
+	type RelayerFee = AssetRelayerFee;
+	#[cfg(not(feature = "with-dex"))]
+	type RelayerFee = ();
+	type Currency = Balances;
+	type RelayerBond = RelayerBond;
+	type MaxRelayerFees = MaxRelayerFees;
+}
+
+/// Dex calls, including those wrapped in forwarded requests.
+#[cfg(feature = "with-dex")]
+pub struct DexCalls;
+
+#[cfg(feature = "with-dex")]
+impl Filter<Call> for DexCalls {
+	fn filter(call: &Call) -> bool {
+		match call {
+			Call::ZenlinkDex(_) => true,
+			Call::Forwarder(pallet_forwarder::Call::forward(request, _)) => Self::filter(&request.call),
+			_ => false,
+		}
+	}
+}
+
+#[cfg(feature = "with-dex")]
+parameter_types! {
+	/// Dex calls can use half of the normal dispatch weight of a block.
+	pub const DexWeightShare: Perbill = Perbill::from_percent(50);
+	/// Puts all other calls ahead of dex calls, whatever their fees.
+	pub const NonDexPriority: TransactionPriority = TransactionPriority::max_value() / 2;
+}
+
+#[cfg(feature = "with-dex")]
+impl pallet_weight_share::Trait for Runtime {
+	type Event = Event;
+	type LimitedCalls = DexCalls;
+	type DefaultShare = DexWeightShare;
+	type UpdateOrigin = EnsureRoot<AccountId>;
+	type OtherCallsPriority = NonDexPriority;
+}
+
+#[cfg(feature = "with-dex")]
+parameter_types! {
+	pub const AuctionModuleId: ModuleId = ModuleId(*b"cvs/auct");
+	pub const AuctionMinimumIncrement: Perbill = Perbill::from_percent(5);
+	pub const AuctionExtendPeriod: BlockNumber = 5 * MINUTES;
+	pub const AuctionMaxPerBlock: u32 = 50;
+}
+
+#[cfg(feature = "with-dex")]
+impl pallet_auction::Trait for Runtime {
+	type Event = Event;
+	type ModuleId = AuctionModuleId;
+	type MinimumIncrement = AuctionMinimumIncrement;
+	type ExtendPeriod = AuctionExtendPeriod;
+	type Handler = ();
+	type CreateOrigin = EnsureSigned<AccountId>;
+	type MaxAuctionsPerBlock = AuctionMaxPerBlock;
+}
+
+#[cfg(feature = "with-dex")]
+parameter_types! {
+	pub const SettlementMaxBatchLength: u32 = 64;
+}
+
+#[cfg(feature = "with-dex")]
+impl pallet_settlement::Trait for Runtime {
+	type Event = Event;
+	type Signature = Signature;
+	type Signer = <Signature as Verify>::Signer;
+	type MaxBatchLength = SettlementMaxBatchLength;
+}
+
+#[cfg(feature = "with-dex")]
+parameter_types! {
+	pub const ReservesHistoryDepth: u32 = 30;
+	pub const ReservesMaxReferenceLength: u32 = 128;
+}
+
+/// An attestation of the reserves of a wrapped asset.
+#[cfg(feature = "with-dex")]
+pub type Attestation = pallet_reserves::AttestationOf<Runtime>;
+
+#[cfg(feature = "with-dex")]
+impl pallet_reserves::Trait for Runtime {
+	type Event = Event;
+	type Signature = Signature;
+	type Signer = <Signature as Verify>::Signer;
+	type AdminOrigin = EnsureRoot<AccountId>;
+	type HistoryDepth = ReservesHistoryDepth;
+	type MaxReferenceLength = ReservesMaxReferenceLength;
+}
+
+#[cfg(feature = "with-dex")]
+parameter_types! {
+	pub const BridgeChainId: u8 = 1;
+	pub const BridgeProposalLifetime: BlockNumber = HOURS;
+}
+
+#[cfg(feature = "with-dex")]
+impl pallet_bridge::Trait for Runtime {
+	type Event = Event;
+	type AdminOrigin = EnsureRoot<AccountId>;
+	type ChainId = BridgeChainId;
+	type ProposalLifetime = BridgeProposalLifetime;
+	type ReserveGuard = Reserves;
+}
+
+#[cfg(feature = "with-dex")]
+parameter_types! {
+	pub const InvoiceMaxMemoLength: u32 = 64;
+	pub const InvoiceDeposit: Balance = DOLLARS;
+}
+
+#[cfg(feature = "with-dex")]
+impl pallet_invoice::Trait for Runtime {
+	type Event = Event;
+	type MaxMemoLength = InvoiceMaxMemoLength;
+	type Currency = Balances;
+	type InvoiceDeposit = InvoiceDeposit;
+}
+
+#[cfg(feature = "with-dex")]
+parameter_types! {
+	pub const RescueDelay: BlockNumber = 2 * DAYS;
+	/// Module accounts only receive assets by mistake; escrows like the auction's are excluded.
+	pub RescuableModules: Vec<ModuleId> = vec![DEXModuleId::get()];
+}
+
+/// Assets traded by a zenlink exchange.
+#[cfg(feature = "with-dex")]
+pub struct DexPooledAssets;
+
+#[cfg(feature = "with-dex")]
+impl pallet_rescue::PooledAssets<AssetId> for DexPooledAssets {
+	fn is_pooled(asset_id: &AssetId) -> bool {
+		ZenlinkDex::get_exchange_by_token_id(*asset_id).is_some()
+	}
+}
+
+#[cfg(feature = "with-dex")]
+impl pallet_rescue::Trait for Runtime {
+	type Event = Event;
+	type RescueOrigin = EnsureRoot<AccountId>;
+	type RescueDelay = RescueDelay;
+	type RescuableModules = RescuableModules;
+	type PooledAssets = DexPooledAssets;
+}
+
+#[cfg(feature = "with-dex")]
+parameter_types! {
+	pub const SweepMinimumDelay: BlockNumber = DAYS;
+}
+
+#[cfg(feature = "with-dex")]
+impl pallet_sweep::Trait for Runtime {
+	type Event = Event;
+	type MinimumDelay = SweepMinimumDelay;
+}
+
 // Create the runtime by composing the FRAME pallets that were previously configured.
-construct_runtime!(
-	pub enum Runtime where
-		Block = Block,
-		NodeBlock = opaque::Block,
-		UncheckedExtrinsic = UncheckedExtrinsic
+//
+// The zenlink pallets and the pallets built on them are only included with the `with-dex`
+// feature. They are passed in at their usual positions, so module indices stay the same.
+macro_rules! runtime {
+	({ $($dex:tt)* } { $($dex_pallets:tt)* }) => {
+		construct_runtime!(
+			pub enum Runtime where
+				Block = Block,
+				NodeBlock = opaque::Block,
+				UncheckedExtrinsic = UncheckedExtrinsic
+			{
+				System: frame_system::{Module, Call, Config, Storage, Event<T>},
+				RandomnessCollectiveFlip: pallet_randomness_collective_flip::{Module, Call, Storage},
+				Timestamp: pallet_timestamp::{Module, Call, Storage, Inherent},
+				Aura: pallet_aura::{Module, Config<T>, Inherent},
+				Grandpa: pallet_grandpa::{Module, Call, Storage, Config, Event},
+				Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
+				TransactionPayment: pallet_transaction_payment::{Module, Storage},
+				Sudo: pallet_sudo::{Module, Call, Config<T>, Storage, Event<T>},
+				Contracts: pallet_contracts::{Module, Call, Config, Storage, Event<T>},
+				$($dex)*
+				Referral: pallet_referral::{Module, Call, Storage, Event<T>},
+				Forwarder: pallet_forwarder::{Module, Call, Storage, Event<T>},
+				$($dex_pallets)*
+			}
+		);
+	};
+}
+
+#[cfg(feature = "with-dex")]
+runtime! {
 	{
-		System: frame_system::{Module, Call, Config, Storage, Event<T>},
-		RandomnessCollectiveFlip: pallet_randomness_collective_flip::{Module, Call, Storage},
-		Timestamp: pallet_timestamp::{Module, Call, Storage, Inherent},
-		Aura: pallet_aura::{Module, Config<T>, Inherent},
-		Grandpa: pallet_grandpa::{Module, Call, Storage, Config, Event},
-		Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
-		TransactionPayment: pallet_transaction_payment::{Module, Storage},
-		Sudo: pallet_sudo::{Module, Call, Config<T>, Storage, Event<T>},
-		Contracts: pallet_contracts::{Module, Call, Config, Storage, Event<T>},
 		ZenlinkAssets: zenlink_assets::{Module, Call, Storage, Event<T>},
 		ZenlinkDex: zenlink_dex::{Module, Call, Storage, Event<T>},
 	}
-);
+	{
+		WeightShare: pallet_weight_share::{Module, Call, Storage, Event},
+		Auction: pallet_auction::{Module, Call, Storage, Event<T>},
+		Settlement: pallet_settlement::{Module, Call, Storage, Event<T>},
+		Reserves: pallet_reserves::{Module, Call, Storage, Event<T>},
+		Bridge: pallet_bridge::{Module, Call, Storage, Event<T>},
+		Invoice: pallet_invoice::{Module, Call, Storage, Event<T>},
+		Rescue: pallet_rescue::{Module, Call, Storage, Event<T>},
+		Sweep: pallet_sweep::{Module, Call, Storage, Event<T>},
+	}
+}
+
+#[cfg(not(feature = "with-dex"))]
+runtime! { {} {} }
 
 /// The address format for describing accounts.
 pub type Address = AccountId;
@@ -346,6 +583,19 @@ pub type SignedBlock = generic::SignedBlock<Block>;
 /// BlockId type as expected by this runtime.
 pub type BlockId = generic::BlockId<Block>;
 /// The SignedExtension to the basic transaction logic.
+#[cfg(feature = "with-dex")]
+pub type SignedExtra = (
+	frame_system::CheckSpecVersion<Runtime>,
+	frame_system::CheckTxVersion<Runtime>,
+	frame_system::CheckGenesis<Runtime>,
+	frame_system::CheckEra<Runtime>,
+	frame_system::CheckNonce<Runtime>,
+	frame_system::CheckWeight<Runtime>,
+	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+	pallet_weight_share::CheckWeightShare<Runtime>,
+);
+/// The SignedExtension to the basic transaction logic.
+#[cfg(not(feature = "with-dex"))]
 pub type SignedExtra = (
 	frame_system::CheckSpecVersion<Runtime>,
 	frame_system::CheckTxVersion<Runtime>,
@@ -353,12 +603,37 @@ pub type SignedExtra = (
 	frame_system::CheckEra<Runtime>,
 	frame_system::CheckNonce<Runtime>,
 	frame_system::CheckWeight<Runtime>,
-	pallet_transaction_payment::ChargeTransactionPayment<Runtime>
+	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
 );
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, Call, Signature, SignedExtra>;
 /// Extrinsic type that has already been checked.
 pub type CheckedExtrinsic = generic::CheckedExtrinsic<AccountId, Call, SignedExtra>;
+/// The name of the zenlink module at `index` in `construct_runtime!`.
+///
+/// `PalletInfo` can only be asked about one module type at a time, so names are resolved for the
+/// zenlink modules the dry run is meant for. Error names are resolved for every module.
+#[cfg(feature = "with-dex")]
+fn zenlink_module_name(index: u8) -> Option<&'static str> {
+	use frame_support::traits::PalletInfo as _;
+
+	fn name_if<P: 'static>(index: u8) -> Option<&'static str> {
+		if PalletInfo::index::<P>() == Some(index as usize) {
+			PalletInfo::name::<P>()
+		} else {
+			None
+		}
+	}
+
+	name_if::<ZenlinkAssets>(index).or_else(|| name_if::<ZenlinkDex>(index))
+}
+
+/// The name of the zenlink module at `index` in `construct_runtime!`.
+#[cfg(not(feature = "with-dex"))]
+fn zenlink_module_name(_index: u8) -> Option<&'static str> {
+	None
+}
+
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
 	Runtime,
@@ -368,166 +643,201 @@ pub type Executive = frame_executive::Executive<
 	AllModules,
 >;
 
-impl_runtime_apis! {
-	impl sp_api::Core<Block> for Runtime {
-		fn version() -> RuntimeVersion {
-			VERSION
-		}
-
-		fn execute_block(block: Block) {
-			Executive::execute_block(block)
-		}
-
-		fn initialize_block(header: &<Block as BlockT>::Header) {
-			Executive::initialize_block(header)
-		}
-	}
-
-	impl sp_api::Metadata<Block> for Runtime {
-		fn metadata() -> OpaqueMetadata {
-			Runtime::metadata().into()
-		}
-	}
-
-	impl sp_block_builder::BlockBuilder<Block> for Runtime {
-		fn apply_extrinsic(extrinsic: <Block as BlockT>::Extrinsic) -> ApplyExtrinsicResult {
-			Executive::apply_extrinsic(extrinsic)
-		}
+// Implements the runtime APIs. The APIs of the `with-dex` pallets are passed in when the feature is
+// enabled.
+macro_rules! runtime_apis {
+	($($dex_apis:tt)*) => {
+		impl_runtime_apis! {
+			impl sp_api::Core<Block> for Runtime {
+				fn version() -> RuntimeVersion {
+					VERSION
+				}
+
+				fn execute_block(block: Block) {
+					Executive::execute_block(block)
+				}
+
+				fn initialize_block(header: &<Block as BlockT>::Header) {
+					Executive::initialize_block(header)
+				}
+			}
 
-		fn finalize_block() -> <Block as BlockT>::Header {
-			Executive::finalize_block()
-		}
+			impl sp_api::Metadata<Block> for Runtime {
+				fn metadata() -> OpaqueMetadata {
+					Runtime::metadata().into()
+				}
+			}
 
-		fn inherent_extrinsics(data: sp_inherents::InherentData) -> Vec<<Block as BlockT>::Extrinsic> {
-			data.create_extrinsics()
-		}
+			impl sp_block_builder::BlockBuilder<Block> for Runtime {
+				fn apply_extrinsic(extrinsic: <Block as BlockT>::Extrinsic) -> ApplyExtrinsicResult {
+					Executive::apply_extrinsic(extrinsic)
+				}
+
+				fn finalize_block() -> <Block as BlockT>::Header {
+					Executive::finalize_block()
+				}
+
+				fn inherent_extrinsics(data: sp_inherents::InherentData) -> Vec<<Block as BlockT>::Extrinsic> {
+					data.create_extrinsics()
+				}
+
+				fn check_inherents(
+					block: Block,
+					data: sp_inherents::InherentData,
+				) -> sp_inherents::CheckInherentsResult {
+					data.check_extrinsics(&block)
+				}
+
+				fn random_seed() -> <Block as BlockT>::Hash {
+					RandomnessCollectiveFlip::random_seed()
+				}
+			}
 
-		fn check_inherents(
-			block: Block,
-			data: sp_inherents::InherentData,
-		) -> sp_inherents::CheckInherentsResult {
-			data.check_extrinsics(&block)
-		}
+			impl sp_transaction_pool::runtime_api::TaggedTransactionQueue<Block> for Runtime {
+				fn validate_transaction(
+					source: TransactionSource,
+					tx: <Block as BlockT>::Extrinsic,
+				) -> TransactionValidity {
+					Executive::validate_transaction(source, tx)
+				}
+			}
 
-		fn random_seed() -> <Block as BlockT>::Hash {
-			RandomnessCollectiveFlip::random_seed()
-		}
-	}
+			impl sp_offchain::OffchainWorkerApi<Block> for Runtime {
+				fn offchain_worker(header: &<Block as BlockT>::Header) {
+					Executive::offchain_worker(header)
+				}
+			}
 
-	impl sp_transaction_pool::runtime_api::TaggedTransactionQueue<Block> for Runtime {
-		fn validate_transaction(
-			source: TransactionSource,
-			tx: <Block as BlockT>::Extrinsic,
-		) -> TransactionValidity {
-			Executive::validate_transaction(source, tx)
-		}
-	}
+			impl sp_consensus_aura::AuraApi<Block, AuraId> for Runtime {
+				fn slot_duration() -> u64 {
+					Aura::slot_duration()
+				}
 
-	impl sp_offchain::OffchainWorkerApi<Block> for Runtime {
-		fn offchain_worker(header: &<Block as BlockT>::Header) {
-			Executive::offchain_worker(header)
-		}
-	}
+				fn authorities() -> Vec<AuraId> {
+					Aura::authorities()
+				}
+			}
 
-	impl sp_consensus_aura::AuraApi<Block, AuraId> for Runtime {
-		fn slot_duration() -> u64 {
-			Aura::slot_duration()
-		}
+			impl sp_session::SessionKeys<Block> for Runtime {
+				fn generate_session_keys(seed: Option<Vec<u8>>) -> Vec<u8> {
+					opaque::SessionKeys::generate(seed)
+				}
 
-		fn authorities() -> Vec<AuraId> {
-			Aura::authorities()
-		}
-	}
+				fn decode_session_keys(
+					encoded: Vec<u8>,
+				) -> Option<Vec<(Vec<u8>, KeyTypeId)>> {
+					opaque::SessionKeys::decode_into_raw_public_keys(&encoded)
+				}
+			}
 
-	impl sp_session::SessionKeys<Block> for Runtime {
-		fn generate_session_keys(seed: Option<Vec<u8>>) -> Vec<u8> {
-			opaque::SessionKeys::generate(seed)
-		}
+			impl fg_primitives::GrandpaApi<Block> for Runtime {
+				fn grandpa_authorities() -> GrandpaAuthorityList {
+					Grandpa::grandpa_authorities()
+				}
+
+				fn submit_report_equivocation_unsigned_extrinsic(
+					_equivocation_proof: fg_primitives::EquivocationProof<
+						<Block as BlockT>::Hash,
+						NumberFor<Block>,
+					>,
+					_key_owner_proof: fg_primitives::OpaqueKeyOwnershipProof,
+				) -> Option<()> {
+					None
+				}
+
+				fn generate_key_ownership_proof(
+					_set_id: fg_primitives::SetId,
+					_authority_id: GrandpaId,
+				) -> Option<fg_primitives::OpaqueKeyOwnershipProof> {
+					// NOTE: this is the only implementation possible since we've
+					// defined our key owner proof type as a bottom type (i.e. a type
+					// with no values).
+					None
+				}
+			}
 
-		fn decode_session_keys(
-			encoded: Vec<u8>,
-		) -> Option<Vec<(Vec<u8>, KeyTypeId)>> {
-			opaque::SessionKeys::decode_into_raw_public_keys(&encoded)
-		}
-	}
+			impl frame_system_rpc_runtime_api::AccountNonceApi<Block, AccountId, Index> for Runtime {
+				fn account_nonce(account: AccountId) -> Index {
+					System::account_nonce(account)
+				}
+			}
 
-	impl fg_primitives::GrandpaApi<Block> for Runtime {
-		fn grandpa_authorities() -> GrandpaAuthorityList {
-			Grandpa::grandpa_authorities()
-		}
+			impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance> for Runtime {
+				fn query_info(
+					uxt: <Block as BlockT>::Extrinsic,
+					len: u32,
+				) -> pallet_transaction_payment_rpc_runtime_api::RuntimeDispatchInfo<Balance> {
+					TransactionPayment::query_info(uxt, len)
+				}
+			}
 
-		fn submit_report_equivocation_unsigned_extrinsic(
-			_equivocation_proof: fg_primitives::EquivocationProof<
-				<Block as BlockT>::Hash,
-				NumberFor<Block>,
-			>,
-			_key_owner_proof: fg_primitives::OpaqueKeyOwnershipProof,
-		) -> Option<()> {
-			None
-		}
+			impl pallet_contracts_rpc_runtime_api::ContractsApi<Block, AccountId, Balance, BlockNumber>
+				for Runtime
+			{
+				fn call(
+					origin: AccountId,
+					dest: AccountId,
+					value: Balance,
+					gas_limit: u64,
+					input_data: Vec<u8>,
+				) -> ContractExecResult {
+					let (exec_result, gas_consumed) =
+						Contracts::bare_call(origin, dest.into(), value, gas_limit, input_data);
+					match exec_result {
+						Ok(v) => ContractExecResult::Success {
+							flags: v.flags.bits(),
+							data: v.data,
+							gas_consumed: gas_consumed,
+						},
+						Err(_) => ContractExecResult::Error,
+					}
+				}
+
+				fn get_storage(
+					address: AccountId,
+					key: [u8; 32],
+				) -> pallet_contracts_primitives::GetStorageResult {
+					Contracts::get_storage(address, key)
+				}
+
+				fn rent_projection(
+					address: AccountId,
+				) -> pallet_contracts_primitives::RentProjectionResult<BlockNumber> {
+					Contracts::rent_projection(address)
+				}
+			}
 
-		fn generate_key_ownership_proof(
-			_set_id: fg_primitives::SetId,
-			_authority_id: GrandpaId,
-		) -> Option<fg_primitives::OpaqueKeyOwnershipProof> {
-			// NOTE: this is the only implementation possible since we've
-			// defined our key owner proof type as a bottom type (i.e. a type
-			// with no values).
-			None
-		}
-	}
+			impl canvas_dry_run_runtime_api::DryRunApi<Block> for Runtime {
+				fn dry_run(extrinsic: <Block as BlockT>::Extrinsic) -> DryRunResult {
+					match Executive::apply_extrinsic(extrinsic) {
+						Ok(Ok(())) => DryRunResult::Success,
+						Ok(Err(error)) => DryRunResult::Failed(DispatchErrorInfo::new(error, zenlink_module_name)),
+						Err(error) => DryRunResult::Invalid(<&'static str>::from(error).as_bytes().to_vec()),
+					}
+				}
+			}
 
-	impl frame_system_rpc_runtime_api::AccountNonceApi<Block, AccountId, Index> for Runtime {
-		fn account_nonce(account: AccountId) -> Index {
-			System::account_nonce(account)
-		}
-	}
+			impl pallet_referral_runtime_api::ReferralApi<Block, AccountId> for Runtime {
+				fn referrer_of(who: AccountId) -> Option<AccountId> {
+					Referral::referrer(who)
+				}
 
-	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance> for Runtime {
-		fn query_info(
-			uxt: <Block as BlockT>::Extrinsic,
-			len: u32,
-		) -> pallet_transaction_payment_rpc_runtime_api::RuntimeDispatchInfo<Balance> {
-			TransactionPayment::query_info(uxt, len)
-		}
-	}
+				fn referees_of(referrer: AccountId, start: u32, count: u32) -> Vec<AccountId> {
+					Referral::referees(&referrer, start, count)
+				}
 
-	impl pallet_contracts_rpc_runtime_api::ContractsApi<Block, AccountId, Balance, BlockNumber>
-		for Runtime
-	{
-		fn call(
-			origin: AccountId,
-			dest: AccountId,
-			value: Balance,
-			gas_limit: u64,
-			input_data: Vec<u8>,
-		) -> ContractExecResult {
-			let (exec_result, gas_consumed) =
-				Contracts::bare_call(origin, dest.into(), value, gas_limit, input_data);
-			match exec_result {
-				Ok(v) => ContractExecResult::Success {
-					flags: v.flags.bits(),
-					data: v.data,
-					gas_consumed: gas_consumed,
-				},
-				Err(_) => ContractExecResult::Error,
+				fn referee_count(referrer: AccountId) -> u32 {
+					Referral::referee_count(referrer)
+				}
 			}
-		}
-
-		fn get_storage(
-			address: AccountId,
-			key: [u8; 32],
-		) -> pallet_contracts_primitives::GetStorageResult {
-			Contracts::get_storage(address, key)
-		}
 
-		fn rent_projection(
-			address: AccountId,
-		) -> pallet_contracts_primitives::RentProjectionResult<BlockNumber> {
-			Contracts::rent_projection(address)
+			$($dex_apis)*
 		}
-	}
+	};
+}
 
+#[cfg(feature = "with-dex")]
+runtime_apis! {
 	impl zenlink_dex_runtime_api::ZenlinkDexApi<Block, AccountId, AssetId, TokenBalance, Balance, ExchangeId> for Runtime {
 		fn get_token_info(
 			token_id: AssetId
@@ -586,4 +896,73 @@ impl_runtime_apis! {
 			ZenlinkDex::get_exchanges()
 		}
 	}
-}
\ No newline at end of file
+
+	impl canvas_batch_read_runtime_api::BatchReadApi<Block, AccountId, AssetId, TokenBalance, Balance, ExchangeId> for Runtime {
+		fn batch_read(
+			queries: Vec<Query<AccountId, AssetId, ExchangeId>>,
+		) -> Result<Vec<Answer<AccountId, AssetId, TokenBalance, Balance, ExchangeId>>, BatchReadError> {
+			if queries.len() > canvas_batch_read_runtime_api::MAX_QUERIES as usize {
+				return Err(BatchReadError::TooManyQueries);
+			}
+
+			let answers = queries
+				.into_iter()
+				.map(|query| match query {
+					Query::TokenInfo(token_id) => Answer::TokenInfo(ZenlinkDex::get_token_info(token_id)),
+					Query::TokenBalance(token_id, owner) => {
+						Answer::Amount(ZenlinkDex::get_token_balance(token_id, owner))
+					}
+					Query::TokenAllowance(token_id, owner, spender) => {
+						Answer::Amount(ZenlinkDex::get_token_allowance(token_id, owner, spender))
+					}
+					Query::Exchange(id) => Answer::Exchange(ZenlinkDex::get_exchange_by_id(id)),
+					Query::ExchangeByTokenId(token_id) => {
+						Answer::Exchange(ZenlinkDex::get_exchange_by_token_id(token_id))
+					}
+				})
+				.collect();
+			Ok(answers)
+		}
+	}
+
+	impl pallet_reserves_runtime_api::ReservesApi<Block, AssetId, Attestation> for Runtime {
+		fn latest_attestation(asset_id: AssetId) -> Option<Attestation> {
+			Reserves::latest_attestation(&asset_id)
+		}
+
+		fn can_mint(asset_id: AssetId) -> bool {
+			use pallet_reserves::ReserveGuard;
+
+			Reserves::can_mint(&asset_id)
+		}
+	}
+}
+
+#[cfg(not(feature = "with-dex"))]
+runtime_apis! {}
+
+#[cfg(all(test, feature = "with-dex"))]
+mod tests {
+	use super::*;
+	use canvas_batch_read_runtime_api::{runtime_decl_for_BatchReadApi::BatchReadApi, MAX_QUERIES};
+
+	fn batch_read(
+		queries: Vec<Query<AccountId, AssetId, ExchangeId>>,
+	) -> Result<Vec<Answer<AccountId, AssetId, TokenBalance, Balance, ExchangeId>>, BatchReadError> {
+		<Runtime as BatchReadApi<Block, AccountId, AssetId, TokenBalance, Balance, ExchangeId>>::batch_read(queries)
+	}
+
+	#[test]
+	fn batch_read_answers_at_most_max_queries() {
+		sp_io::TestExternalities::default().execute_with(|| {
+			let answers = batch_read((0..MAX_QUERIES).map(Query::TokenInfo).collect()).unwrap();
+			assert_eq!(answers.len(), MAX_QUERIES as usize);
+			assert!(answers.iter().all(|answer| *answer == Answer::TokenInfo(None)));
+
+			assert_eq!(
+				batch_read((0..=MAX_QUERIES).map(Query::TokenInfo).collect()),
+				Err(BatchReadError::TooManyQueries)
+			);
+		});
+	}
+}